@@ -4,7 +4,7 @@ use pprof::criterion::{Output, PProfProfiler};
 
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use bvec::BVec;
+use bvec::{BVec, BVecBuilder};
 
 fn bench_get_bvec(c: &mut Criterion) {
     let mut rng = StdRng::from_seed([0; 32]);
@@ -127,9 +127,173 @@ fn bench_insert(c: &mut Criterion) {
     }
 }
 
+fn bench_append(c: &mut Criterion) {
+    c.bench_function("BVec<i32>::append (1M + 100)", |b| {
+        b.iter_batched(
+            || {
+                let mut long = BVec::<i32>::new();
+                long.extend(0..1_000_000);
+                let mut short = BVec::<i32>::new();
+                short.extend(0..100);
+                (long, short)
+            },
+            |(mut long, mut short)| {
+                long.append(&mut short);
+                long
+            },
+            BatchSize::PerIteration,
+        )
+    });
+}
+
+fn bench_extend_from_slice(c: &mut Criterion) {
+    let slice: Vec<i32> = (0..100_000).collect();
+
+    c.bench_function("BVec<i32>::extend_from_slice (100k + 100k)", |b| {
+        b.iter_batched(
+            || (0..100_000).collect::<BVec<i32>>(),
+            |mut bvec| {
+                bvec.extend_from_slice(&slice);
+                bvec
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    c.bench_function("BVec<i32>::extend (100k + 100k)", |b| {
+        b.iter_batched(
+            || (0..100_000).collect::<BVec<i32>>(),
+            |mut bvec| {
+                bvec.extend(slice.iter().copied());
+                bvec
+            },
+            BatchSize::PerIteration,
+        )
+    });
+}
+
+fn bench_append_from_iter(c: &mut Criterion) {
+    c.bench_function("BVec<i32>::append_from_iter (1M + 1M)", |b| {
+        b.iter_batched(
+            || (0..1_000_000).collect::<BVec<i32>>(),
+            |mut bvec| {
+                bvec.append_from_iter(0..1_000_000);
+                bvec
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    c.bench_function("BVec<i32>::extend (1M + 1M)", |b| {
+        b.iter_batched(
+            || (0..1_000_000).collect::<BVec<i32>>(),
+            |mut bvec| {
+                bvec.extend(0..1_000_000);
+                bvec
+            },
+            BatchSize::PerIteration,
+        )
+    });
+}
+
+fn bench_iter_nth(c: &mut Criterion) {
+    let mut bvec = BVec::<i32>::new();
+    bvec.extend(0..1_000_000);
+
+    c.bench_function("BVec<i32>::iter().nth(500_000)", |b| {
+        b.iter(|| bvec.iter().nth(500_000))
+    });
+
+    c.bench_function("BVec<i32>::iter() next 500_000 times", |b| {
+        b.iter(|| {
+            let mut it = bvec.iter();
+            for _ in 0..500_000 {
+                it.next();
+            }
+            it.next()
+        })
+    });
+}
+
+fn bench_iter_find(c: &mut Criterion) {
+    let mut bvec = BVec::<i32>::new();
+    bvec.extend(0..1_000_000);
+
+    c.bench_function("BVec<i32>::iter().find (1M)", |b| {
+        b.iter(|| bvec.iter().find(|&&x| x == 999_999).copied())
+    });
+
+    c.bench_function("BVec<i32>::iter() next until found (1M)", |b| {
+        b.iter(|| {
+            let mut it = bvec.iter();
+            loop {
+                let &x = it.next()?;
+                if x == 999_999 {
+                    return Some(x);
+                }
+            }
+        })
+    });
+}
+
+fn bench_iter_fold(c: &mut Criterion) {
+    let mut bvec = BVec::<i32>::new();
+    bvec.extend(0..1_000_000);
+
+    c.bench_function("BVec<i32>::iter().fold(0, +) (1M)", |b| {
+        b.iter(|| bvec.iter().fold(0i32, |acc, x| acc.wrapping_add(*x)))
+    });
+
+    c.bench_function("BVec<i32>::iter() next and add (1M)", |b| {
+        b.iter(|| {
+            let mut acc = 0i32;
+            for x in bvec.iter() {
+                acc = acc.wrapping_add(*x);
+            }
+            acc
+        })
+    });
+}
+
+fn bench_builder(c: &mut Criterion) {
+    for size in [1_000, 100_000, 1_000_000] {
+        c.bench_with_input(
+            BenchmarkId::new("BVecBuilder<i32>::push", size),
+            &size,
+            |b, &s| {
+                b.iter(|| {
+                    let mut builder = BVecBuilder::new();
+                    for x in 0..s {
+                        builder.push(x);
+                    }
+                    builder.finish()
+                })
+            },
+        );
+
+        c.bench_with_input(
+            BenchmarkId::new("BVec<i32>::from_iter", size),
+            &size,
+            |b, &s| b.iter(|| (0..s).collect::<BVec<i32>>()),
+        );
+
+        c.bench_with_input(
+            BenchmarkId::new("BVec<i32>::extend", size),
+            &size,
+            |b, &s| {
+                b.iter(|| {
+                    let mut bvec = BVec::new();
+                    bvec.extend(0..s);
+                    bvec
+                })
+            },
+        );
+    }
+}
+
 criterion_group!(
     name = benches;
     config = Criterion::default().sample_size(500).with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
-    targets = bench_get_bvec, bench_get_vec, bench_get_im_vec, bench_insert
+    targets = bench_get_bvec, bench_get_vec, bench_get_im_vec, bench_insert, bench_append, bench_extend_from_slice, bench_append_from_iter, bench_iter_nth, bench_iter_find, bench_iter_fold, bench_builder
 );
 criterion_main!(benches);
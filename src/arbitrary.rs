@@ -0,0 +1,18 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::BVec;
+
+impl<'a, T: Arbitrary<'a>, const B: usize, const C: usize> Arbitrary<'a> for BVec<T, B, C> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.arbitrary_iter()?.collect()
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        u.arbitrary_take_rest_iter()?.collect()
+    }
+
+    #[inline]
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
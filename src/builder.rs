@@ -0,0 +1,194 @@
+use core::mem;
+
+use crate::{
+    node::{
+        handle::{height, InternalMut, LeafMut, Node},
+        InternalNode, NodeBase, NodePtr, RawNodeWithLen,
+    },
+    ownership,
+    panics::panic_length_overflow,
+    BVec, DEFAULT_BRANCH_FACTOR, DEFAULT_LEAF_BYTES,
+};
+
+/// Builds a `BVec` from a stream of elements pushed to its back.
+///
+/// Unlike pushing to a `BVec`, the builder never splits or rebalances nodes
+/// while elements are being added. Leaves are filled up completely and then
+/// promoted onto the right spine of the tree, which only gets balanced once
+/// in [`finish`](BVecBuilder::finish).
+pub struct BVecBuilder<
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    tree: BVec<T, B, C>,
+    leaf: Option<NodePtr<T, B, C>>,
+}
+
+impl<T> BVecBuilder<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::new_with_params()
+    }
+}
+
+impl<T, const B: usize, const C: usize> BVecBuilder<T, B, C> {
+    /// Like [`new`](BVecBuilder::new), but for any node sizes.
+    #[must_use]
+    #[inline]
+    pub const fn new_with_params() -> Self {
+        Self {
+            tree: BVec::new_with_params(),
+            leaf: None,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let leaf_len = self
+            .leaf
+            .map_or(0, |leaf| unsafe { LeafMut::new(leaf).len() });
+        self.tree.len() + leaf_len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.leaf.is_none() && self.tree.is_empty()
+    }
+
+    /// # Panics
+    /// Panics if the length of the sequence would overflow.
+    pub fn push(&mut self, value: T) {
+        if self.len() == isize::MAX as usize {
+            panic_length_overflow();
+        }
+
+        let leaf = *self.leaf.get_or_insert_with(NodeBase::new_leaf);
+        let mut handle = unsafe { LeafMut::new(leaf) };
+        handle.values_mut().insert(handle.len(), value);
+
+        if handle.is_full() {
+            self.leaf = None;
+            unsafe { self.push_node(RawNodeWithLen(handle.len(), leaf)) };
+        }
+    }
+
+    #[must_use]
+    pub fn finish(mut self) -> BVec<T, B, C> {
+        self.build()
+    }
+
+    fn build(&mut self) -> BVec<T, B, C> {
+        if let Some(leaf) = self.leaf.take() {
+            let len = unsafe { LeafMut::new(leaf).len() };
+            unsafe { self.push_node(RawNodeWithLen(len, leaf)) };
+        }
+        if let Some(root) = self.tree.root() {
+            unsafe { fix_right_spine(root) };
+        }
+        mem::take(&mut self.tree)
+    }
+
+    /// Appends a node to the rightmost end of its level.
+    ///
+    /// If the node that should become the parent is full, a new parent
+    /// with `node` as its only child is pushed to the level above instead.
+    unsafe fn push_node(&mut self, node: RawNodeWithLen<T, B, C>) {
+        let Some(root) = self.tree.root() else {
+            self.tree.root.write(node.1);
+            self.tree.len = node.0;
+            return;
+        };
+
+        let len = node.0;
+        let height = unsafe { node.1.as_ref().height() };
+        let root_height = unsafe { root.as_ref().height() };
+
+        if height == root_height {
+            let old_root = RawNodeWithLen(self.tree.len, root);
+            self.tree
+                .root
+                .write(InternalNode::from_child_array([old_root, node]));
+            self.tree.len += len;
+            return;
+        }
+
+        let mut parent = root;
+        for _ in height + 1..root_height {
+            let mut handle = unsafe { InternalMut::new(parent) };
+            parent = handle.children()[handle.len_children() - 1];
+        }
+
+        let mut handle = unsafe { InternalMut::new(parent) };
+        if handle.is_full() {
+            let new_parent = InternalNode::new(height + 1);
+            unsafe {
+                InternalMut::new(new_parent).push_back_child(node);
+                self.push_node(RawNodeWithLen(len, new_parent));
+            }
+            return;
+        }
+
+        unsafe {
+            handle.push_back_child(node);
+            let mut node = parent;
+            while let Some(parent) = node.as_ref().parent {
+                let index = node.as_ref().parent_index.assume_init().into();
+                InternalMut::new(parent).add_length_wrapping(index, len);
+                node = parent;
+            }
+        }
+        self.tree.len += len;
+    }
+}
+
+/// Makes the rightmost node of every level non-underfull.
+///
+/// Every other node on a level was full when it was pushed, so the
+/// rightmost one can always steal from its left sibling without merging.
+unsafe fn fix_right_spine<T, const B: usize, const C: usize>(root: NodePtr<T, B, C>) {
+    let mut node = root;
+    loop {
+        let height = unsafe { node.as_ref().height() };
+        if height == 0 {
+            return;
+        }
+
+        let mut handle = unsafe { InternalMut::new(node) };
+        let mut index = handle.len_children() - 1;
+        let last_child = handle.children()[index];
+
+        if height == 1 {
+            let mut parent = unsafe { Node::<ownership::Mut, height::One, T, B, C>::new(node) };
+            while parent.child_mut(index).is_underfull() {
+                let mut child_index = 0;
+                parent.handle_underfull_leaf_child_tail(&mut index, &mut child_index);
+            }
+        } else {
+            let mut parent =
+                unsafe { Node::<ownership::Mut, height::TwoOrMore, T, B, C>::new(node) };
+            while parent.maybe_handle_underfull_child(index) {}
+        }
+
+        node = last_child;
+    }
+}
+
+impl<T, const B: usize, const C: usize> Drop for BVecBuilder<T, B, C> {
+    fn drop(&mut self) {
+        drop(self.build());
+    }
+}
+
+impl<T, const B: usize, const C: usize> Default for BVecBuilder<T, B, C> {
+    fn default() -> Self {
+        Self::new_with_params()
+    }
+}
+
+impl<T, const B: usize, const C: usize> Extend<T> for BVecBuilder<T, B, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.push(value));
+    }
+}
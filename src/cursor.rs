@@ -1,29 +1,37 @@
-use core::{marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+use core::{
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ptr::NonNull,
+};
 
 use crate::{
     node::{
         handle::{
             height, Internal, InternalMut, InternalRef, Leaf, LeafMut, LeafRef, Node, SplitResult,
         },
+        search::partition_point_in_leaf,
+        split::split_at_leaf,
         InternalNode, NodeBase, NodePtr, RawNodeWithLen,
     },
     ownership,
-    panics::panic_length_overflow,
-    BVec,
+    panics::{panic_cursor_out_of_bounds, panic_length_overflow, panic_out_of_bounds},
+    BVec, DEFAULT_BRANCH_FACTOR, DEFAULT_LEAF_BYTES,
 };
 
 // TODO: auto traits: Send, Sync, Unpin, UnwindSafe?
-pub struct CursorInner<'a, O, T: 'a>
+pub struct CursorInner<'a, O, T: 'a, const B: usize, const C: usize>
 where
     O: ownership::Reference<'a, T>,
 {
-    tree: NonNull<BVec<T>>,
-    leaf: MaybeUninit<NodePtr<T>>,
+    tree: NonNull<BVec<T, B, C>>,
+    leaf: MaybeUninit<NodePtr<T, B, C>>,
     pub leaf_index: usize,
     _marker: PhantomData<(&'a (), O)>,
 }
 
-impl<'a, T> Clone for CursorInner<'a, ownership::Immut<'a>, T> {
+impl<'a, T, const B: usize, const C: usize> Clone
+    for CursorInner<'a, ownership::Immut<'a>, T, B, C>
+{
     fn clone(&self) -> Self {
         Self {
             leaf: self.leaf,
@@ -34,23 +42,81 @@ impl<'a, T> Clone for CursorInner<'a, ownership::Immut<'a>, T> {
     }
 }
 
-pub struct Cursor<'a, T> {
-    inner: CursorInner<'a, ownership::Immut<'a>, T>,
+pub struct Cursor<
+    'a,
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    inner: CursorInner<'a, ownership::Immut<'a>, T, B, C>,
     index: usize,
 }
 
-impl<'a, T> Cursor<'a, T> {
-    pub(crate) fn new(tree: &'a BVec<T>, index: usize) -> Self {
+impl<'a, T, const B: usize, const C: usize> Cursor<'a, T, B, C> {
+    pub(crate) fn new(tree: &'a BVec<T, B, C>, index: usize) -> Self {
         let inner = CursorInner::new(tree, index);
         Self { inner, index }
     }
 
+    pub(crate) fn new_front(tree: &'a BVec<T, B, C>) -> Self {
+        let inner = CursorInner::try_new_inbounds_first(tree)
+            .unwrap_or_else(|| CursorInner::new_past_the_end(tree));
+        Self { inner, index: 0 }
+    }
+
+    pub(crate) fn new_back(tree: &'a BVec<T, B, C>) -> Self {
+        if tree.is_empty() {
+            return Self::new_front(tree);
+        }
+        let inner = unsafe { CursorInner::new_last_unchecked(tree) };
+        let index = tree.len() - 1;
+        Self { inner, index }
+    }
+
+    pub(crate) fn find<P>(tree: &'a BVec<T, B, C>, mut pred: P) -> Option<Self>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut inner = CursorInner::<ownership::Immut, T, B, C>::try_new_inbounds_first(tree)?;
+        for index in 0..tree.len() {
+            if pred(unsafe { inner.get_unchecked() }) {
+                return Some(Self { inner, index });
+            }
+            if index + 1 < tree.len() {
+                inner.move_next_inbounds_unchecked();
+            }
+        }
+        None
+    }
+
     #[must_use]
     pub fn get(&self) -> Option<&'a T> {
         self.is_inbounds()
             .then(|| unsafe { self.inner.get_unchecked() })
     }
 
+    /// Returns the element after the cursor without moving it.
+    #[must_use]
+    pub fn peek_next(&self) -> Option<&'a T> {
+        (self.index + 1 < self.len()).then(|| {
+            let mut inner = self.inner.clone();
+            inner.move_next_inbounds_unchecked();
+            unsafe { inner.get_unchecked() }
+        })
+    }
+
+    /// Returns the element before the cursor without moving it.
+    #[must_use]
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        (self.index > 0).then(|| {
+            let mut inner = self.inner.clone();
+            unsafe {
+                inner.move_prev_inbounds_unchecked();
+                inner.get_unchecked()
+            }
+        })
+    }
+
     #[must_use]
     #[inline]
     pub fn is_inbounds(&self) -> bool {
@@ -63,35 +129,113 @@ impl<'a, T> Cursor<'a, T> {
         self.inner.len()
     }
 
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     #[must_use]
     #[inline]
     pub fn index(&self) -> usize {
         self.index
     }
 
-    pub fn move_(&mut self, offset: isize) {
-        self.index = self.index.wrapping_add(offset as usize);
+    /// Returns the number of elements at and after the cursor.
+    #[must_use]
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.len() - self.index
+    }
 
-        if self.index < self.len() {
-            unsafe {
-                self.inner.move_inbounds_unchecked(offset);
+    /// Returns `true` if the cursor is past the end of the tree.
+    #[must_use]
+    #[inline]
+    pub fn is_past_end(&self) -> bool {
+        self.index == self.len()
+    }
+
+    /// Moves the cursor by `offset` elements. Moving to `self.len()` places
+    /// the cursor past the end.
+    ///
+    /// # Panics
+    /// Panics if the new position is before the start or past the end.
+    pub fn move_(&mut self, offset: isize) {
+        let len = self.len();
+        match self.index.checked_add_signed(offset) {
+            Some(index) if index < len => {
+                self.index = index;
+                unsafe { self.inner.move_inbounds_unchecked(offset) };
+            }
+            Some(index) if index == len => {
+                self.index = index;
+                self.inner = unsafe { CursorInner::new_past_the_end(self.inner.tree.as_ref()) };
             }
-        } else if self.index == self.len() {
-            self.inner = unsafe { CursorInner::new_past_the_end(self.inner.tree.as_ref()) };
+            _ => panic_cursor_out_of_bounds(self.index, offset, len),
+        }
+    }
+
+    /// Moves the cursor to `index`, or past the end if `index` is the
+    /// length of the tree.
+    ///
+    /// Instead of searching from the root, this walks up from the current
+    /// leaf only as far as needed, so nearby targets are found quickly.
+    ///
+    /// # Panics
+    /// Panics if `index > self.len()`.
+    pub fn seek_to(&mut self, index: usize) {
+        if index > self.len() {
+            panic_out_of_bounds(index, self.len());
+        }
+        // Both indices are at most `isize::MAX`, so the difference fits.
+        self.move_(index.wrapping_sub(self.index) as isize);
+    }
+
+    /// Moves the cursor to the next element, or past the end if it is at
+    /// the last element.
+    ///
+    /// # Panics
+    /// Panics if the cursor is already past the end.
+    pub fn move_next(&mut self) {
+        let len = self.len();
+        if self.index + 1 < len {
+            self.inner.move_next_inbounds_unchecked();
+        } else if self.index + 1 == len {
+            // The last element is in the last leaf, so the past-the-end
+            // position is right after it.
+            self.inner.leaf_index += 1;
         } else {
-            panic!()
+            panic_cursor_out_of_bounds(self.index, 1, len);
+        }
+        self.index += 1;
+    }
+
+    /// Moves the cursor to the previous element.
+    ///
+    /// # Panics
+    /// Panics if the cursor is at the start.
+    pub fn move_prev(&mut self) {
+        if self.index == 0 {
+            panic_cursor_out_of_bounds(self.index, -1, self.len());
         }
+        self.index -= 1;
+        unsafe { self.inner.move_prev_inbounds_unchecked() };
     }
 }
 
-pub struct CursorMut<'a, T> {
-    inner: CursorInner<'a, ownership::Mut<'a>, T>,
+pub struct CursorMut<
+    'a,
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    inner: CursorInner<'a, ownership::Mut<'a>, T, B, C>,
     index: usize,
     _invariant: PhantomData<&'a mut T>,
 }
 
-impl<'a, T> CursorMut<'a, T> {
-    pub(crate) fn new(tree: &'a mut BVec<T>, index: usize) -> Self {
+impl<'a, T, const B: usize, const C: usize> CursorMut<'a, T, B, C> {
+    pub(crate) fn new(tree: &'a mut BVec<T, B, C>, index: usize) -> Self {
         let inner = CursorInner::new(tree, index);
         Self {
             inner,
@@ -100,7 +244,66 @@ impl<'a, T> CursorMut<'a, T> {
         }
     }
 
-    // pub fn as_inbounds(&mut self) -> Option<InboundsCursorMut<T>> {
+    pub(crate) fn new_front(tree: &'a mut BVec<T, B, C>) -> Self {
+        let inner = if tree.is_empty() {
+            CursorInner::new_past_the_end(tree)
+        } else {
+            unsafe { CursorInner::try_new_inbounds_first(tree).unwrap_unchecked() }
+        };
+        Self {
+            inner,
+            index: 0,
+            _invariant: PhantomData,
+        }
+    }
+
+    pub(crate) fn new_back(tree: &'a mut BVec<T, B, C>) -> Self {
+        if tree.is_empty() {
+            return Self::new_front(tree);
+        }
+        let index = tree.len() - 1;
+        let inner = unsafe { CursorInner::new_last_unchecked(tree) };
+        Self {
+            inner,
+            index,
+            _invariant: PhantomData,
+        }
+    }
+
+    pub(crate) fn new_partition_point<P>(tree: &'a mut BVec<T, B, C>, pred: P) -> Self
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let (inner, index) = CursorInner::new_partition_point(tree, pred);
+        Self {
+            inner,
+            index,
+            _invariant: PhantomData,
+        }
+    }
+
+    pub(crate) fn find<P>(tree: &'a mut BVec<T, B, C>, mut pred: P) -> Option<Self>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let len = tree.len();
+        let mut inner = CursorInner::<ownership::Mut, T, B, C>::try_new_inbounds_first(tree)?;
+        for index in 0..len {
+            if pred(unsafe { inner.get_unchecked() }) {
+                return Some(Self {
+                    inner,
+                    index,
+                    _invariant: PhantomData,
+                });
+            }
+            if index + 1 < len {
+                inner.move_next_inbounds_unchecked();
+            }
+        }
+        None
+    }
+
+    // pub fn as_inbounds(&mut self) -> Option<InboundsCursorMut<T, B, C>> {
     //     self.is_inbounds().then(|| 
     //         InboundsCursorMut { inner: self.inner }
     //     )
@@ -118,49 +321,177 @@ impl<'a, T> CursorMut<'a, T> {
             .then(|| unsafe { self.inner.get_unchecked_mut() })
     }
 
+    /// Returns the element after the cursor without moving it.
+    #[must_use]
+    pub fn peek_next(&self) -> Option<&T> {
+        (self.index + 1 < self.len()).then(|| {
+            let mut inner = unsafe { self.inner.reborrow() };
+            inner.move_next_inbounds_unchecked();
+            unsafe { inner.get_unchecked() }
+        })
+    }
+
+    /// Returns the element before the cursor without moving it.
+    #[must_use]
+    pub fn peek_prev(&self) -> Option<&T> {
+        (self.index > 0).then(|| unsafe {
+            let mut inner = self.inner.reborrow();
+            inner.move_prev_inbounds_unchecked();
+            inner.get_unchecked()
+        })
+    }
+
     #[must_use]
     #[inline]
     pub fn is_inbounds(&self) -> bool {
         self.index < self.len()
     }
 
+    /// Removes the element at the cursor and returns it. The cursor then
+    /// points at the element that followed it.
+    ///
+    /// # Panics
+    /// Panics if the cursor is past the end.
     pub fn remove(&mut self) -> T {
         self.inner.remove()
     }
 
+    /// Inserts `value` at the position of the cursor. The cursor then points
+    /// at the inserted element.
+    ///
+    /// # Panics
+    /// Panics if the length of the tree would overflow.
     pub fn insert(&mut self, value: T) {
         self.inner.insert(value);
     }
 
+    /// Splits the tree at the cursor. The elements before the cursor stay
+    /// in the tree and the rest are returned, so a past-the-end cursor
+    /// returns an empty `BVec`.
+    ///
+    /// This is the same as [`BVec::split_off`] at the index of the cursor,
+    /// but it does not need to search for the leaf to split.
+    #[must_use]
+    pub fn split(self) -> BVec<T, B, C> {
+        let len = self.len();
+        let tree = unsafe { &mut *self.inner.tree.as_ptr() };
+        if self.index == len {
+            return BVec::new_with_params();
+        }
+        if self.index == 0 {
+            return mem::take(tree);
+        }
+
+        let (left, right) =
+            unsafe { split_at_leaf(self.inner.leaf.assume_init(), self.inner.leaf_index) };
+        tree.root.write(left.1);
+        tree.len = left.0;
+        BVec {
+            root: MaybeUninit::new(right.1),
+            len: right.0,
+        }
+    }
+
     #[must_use]
     pub fn len(&self) -> usize {
         self.inner.len()
     }
 
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     #[must_use]
     pub fn index(&self) -> usize {
         self.index
     }
 
+    /// Returns the number of elements at and after the cursor.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.len() - self.index
+    }
+
+    /// Returns `true` if the cursor is past the end of the tree.
+    #[must_use]
+    pub fn is_past_end(&self) -> bool {
+        self.index == self.len()
+    }
+
+    /// Moves the cursor by `offset` elements. Moving to `self.len()` places
+    /// the cursor past the end.
+    ///
+    /// # Panics
+    /// Panics if the new position is before the start or past the end.
     pub fn move_(&mut self, offset: isize) {
-        self.index = self.index.wrapping_add(offset as usize);
-        if self.index < self.len() {
-            unsafe {
-                self.inner.move_inbounds_unchecked(offset);
+        let len = self.len();
+        match self.index.checked_add_signed(offset) {
+            Some(index) if index < len => {
+                self.index = index;
+                unsafe { self.inner.move_inbounds_unchecked(offset) };
+            }
+            Some(index) if index == len => {
+                self.index = index;
+                self.inner = unsafe { CursorInner::new_past_the_end(self.inner.tree.as_mut()) };
             }
-        } else if self.index == self.len() {
-            self.inner = unsafe { CursorInner::new_past_the_end(self.inner.tree.as_mut()) };
+            _ => panic_cursor_out_of_bounds(self.index, offset, len),
+        }
+    }
+
+    /// Moves the cursor to `index`, or past the end if `index` is the
+    /// length of the tree.
+    ///
+    /// Instead of searching from the root, this walks up from the current
+    /// leaf only as far as needed, so nearby targets are found quickly.
+    ///
+    /// # Panics
+    /// Panics if `index > self.len()`.
+    pub fn seek_to(&mut self, index: usize) {
+        if index > self.len() {
+            panic_out_of_bounds(index, self.len());
+        }
+        // Both indices are at most `isize::MAX`, so the difference fits.
+        self.move_(index.wrapping_sub(self.index) as isize);
+    }
+
+    /// Moves the cursor to the next element, or past the end if it is at
+    /// the last element.
+    ///
+    /// # Panics
+    /// Panics if the cursor is already past the end.
+    pub fn move_next(&mut self) {
+        let len = self.len();
+        if self.index + 1 < len {
+            self.inner.move_next_inbounds_unchecked();
+        } else if self.index + 1 == len {
+            // The last element is in the last leaf, so the past-the-end
+            // position is right after it.
+            self.inner.leaf_index += 1;
         } else {
-            panic!()
+            panic_cursor_out_of_bounds(self.index, 1, len);
+        }
+        self.index += 1;
+    }
+
+    /// Moves the cursor to the previous element.
+    ///
+    /// # Panics
+    /// Panics if the cursor is at the start.
+    pub fn move_prev(&mut self) {
+        if self.index == 0 {
+            panic_cursor_out_of_bounds(self.index, -1, self.len());
         }
+        self.index -= 1;
+        unsafe { self.inner.move_prev_inbounds_unchecked() };
     }
 }
 
-impl<'a, O, T> CursorInner<'a, O, T>
+impl<'a, O, T, const B: usize, const C: usize> CursorInner<'a, O, T, B, C>
 where
     O: ownership::Reference<'a, T>,
 {
-    pub(crate) fn new(tree: O::RefTy<'a, BVec<T>>, index: usize) -> Self {
+    pub(crate) fn new(tree: O::RefTy<'a, BVec<T, B, C>>, index: usize) -> Self {
         if let Some(c) = Self::try_new_inbounds(unsafe { core::ptr::read(&tree) }, index) {
             c
         } else if index == O::as_ref(&tree).len() {
@@ -170,7 +501,42 @@ where
         }
     }
 
-    pub(crate) fn try_new_inbounds(tree: O::RefTy<'a, BVec<T>>, mut index: usize) -> Option<Self> {
+    /// Returns a cursor at the partition point of `pred` and its index, see
+    /// [`BVec::partition_point`].
+    pub(crate) fn new_partition_point<P>(
+        tree: O::RefTy<'a, BVec<T, B, C>>,
+        pred: P,
+    ) -> (Self, usize)
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let tree_ref = O::as_ref(&tree);
+        let len = tree_ref.len();
+        let Some(root) = tree_ref.root() else {
+            return (Self::new_past_the_end(tree), 0);
+        };
+
+        let (index, leaf, leaf_index) = unsafe { partition_point_in_leaf(root, pred) };
+        let leaf_len = unsafe { LeafRef::<T, B, C>::new(leaf).len() };
+        // Past the end, the cursor is one past the end of the last leaf. Elsewhere
+        // the point right after a leaf is the start of the next one.
+        let at_leaf_end = leaf_index == leaf_len && index != len;
+        let mut this = Self {
+            tree: tree.into(),
+            leaf_index: leaf_index - usize::from(at_leaf_end),
+            leaf: MaybeUninit::new(leaf),
+            _marker: PhantomData,
+        };
+        if at_leaf_end {
+            this.move_next_inbounds_unchecked();
+        }
+        (this, index)
+    }
+
+    pub(crate) fn try_new_inbounds(
+        tree: O::RefTy<'a, BVec<T, B, C>>,
+        mut index: usize,
+    ) -> Option<Self> {
         if index >= O::as_ref(&tree).len() {
             return None;
         }
@@ -193,7 +559,7 @@ where
         })
     }
 
-    pub(crate) unsafe fn new_last_unchecked(tree: O::RefTy<'a, BVec<T>>) -> Self {
+    pub(crate) unsafe fn new_last_unchecked(tree: O::RefTy<'a, BVec<T, B, C>>) -> Self {
         debug_assert!(O::as_ref(&tree).is_not_empty());
 
         let mut cur_node = unsafe { O::as_ref(&tree).root().unwrap_unchecked() };
@@ -209,7 +575,7 @@ where
             };
         }
 
-        let leaf_index = unsafe { LeafRef::<T>::new(cur_node).len() - 1 };
+        let leaf_index = unsafe { LeafRef::<T, B, C>::new(cur_node).len() - 1 };
         Self {
             tree: tree.into(),
             leaf_index,
@@ -218,7 +584,7 @@ where
         }
     }
 
-    pub(crate) fn new_past_the_end(tree: O::RefTy<'a, BVec<T>>) -> Self {
+    pub(crate) fn new_past_the_end(tree: O::RefTy<'a, BVec<T, B, C>>) -> Self {
         if O::as_ref(&tree).is_empty() {
             return Self {
                 tree: tree.into(),
@@ -232,7 +598,7 @@ where
         this
     }
 
-    pub(crate) fn try_new_inbounds_first(tree: O::RefTy<'a, BVec<T>>) -> Option<Self> {
+    pub(crate) fn try_new_inbounds_first(tree: O::RefTy<'a, BVec<T, B, C>>) -> Option<Self> {
         let mut cur_node = O::as_ref(&tree).root()?;
         let height = unsafe { cur_node.as_ref().height() };
         for _ in 0..height {
@@ -329,7 +695,7 @@ where
                 .into()
         };
         let mut parent = unsafe {
-            InternalRef::<T>::new(self.leaf.assume_init().as_ref().parent.unwrap_unchecked())
+            InternalRef::<T, B, C>::new(self.leaf.assume_init().as_ref().parent.unwrap_unchecked())
         };
         loop {
             offset = unsafe { offset.wrapping_add(parent.sum_lens_below(index)) };
@@ -346,7 +712,7 @@ where
             }
             index = unsafe { parent.node_ptr().as_ref().parent_index.assume_init().into() };
             parent = unsafe {
-                Node::<_, height::Positive, T>::new(
+                Node::<_, height::Positive, T, B, C>::new(
                     parent.node_ptr().as_ref().parent.unwrap_unchecked(),
                 )
             };
@@ -373,7 +739,7 @@ where
                 .into()
         };
         let mut parent = unsafe {
-            InternalMut::<T>::new(self.leaf.assume_init().as_ref().parent.unwrap_unchecked())
+            InternalMut::<T, B, C>::new(self.leaf.assume_init().as_ref().parent.unwrap_unchecked())
         };
         loop {
             if index + 1 < parent.len_children() {
@@ -389,7 +755,7 @@ where
             }
             index = unsafe { parent.node_ptr().as_ref().parent_index.assume_init().into() };
             parent = unsafe {
-                Node::<_, height::Positive, T>::new(
+                Node::<_, height::Positive, T, B, C>::new(
                     parent.node_ptr().as_ref().parent.unwrap_unchecked(),
                 )
             };
@@ -397,13 +763,53 @@ where
         }
     }
 
-    fn leaf(&self) -> Option<LeafRef<T>> {
+    pub unsafe fn move_prev_inbounds_unchecked(&mut self) {
+        // fast path
+        if self.leaf_index > 0 {
+            self.leaf_index -= 1;
+            return;
+        }
+
+        let mut index: usize = unsafe {
+            self.leaf
+                .assume_init()
+                .as_ref()
+                .parent_index
+                .assume_init()
+                .into()
+        };
+        let mut parent = unsafe {
+            InternalMut::<T, B, C>::new(self.leaf.assume_init().as_ref().parent.unwrap_unchecked())
+        };
+        loop {
+            if index > 0 {
+                let mut cur_node = unsafe { *parent.children()[..].get_unchecked(index - 1) };
+                let height = unsafe { cur_node.as_ref().height() };
+                for _ in 0..height {
+                    let mut handle = unsafe { InternalMut::new(cur_node) };
+                    let len_children = handle.len_children();
+                    cur_node = unsafe { *handle.children()[..].get_unchecked(len_children - 1) };
+                }
+                self.leaf.write(cur_node);
+                self.leaf_index = unsafe { LeafRef::<T, B, C>::new(cur_node).len() - 1 };
+                return;
+            }
+            index = unsafe { parent.node_ptr().as_ref().parent_index.assume_init().into() };
+            parent = unsafe {
+                Node::<_, height::Positive, T, B, C>::new(
+                    parent.node_ptr().as_ref().parent.unwrap_unchecked(),
+                )
+            };
+        }
+    }
+
+    fn leaf(&self) -> Option<LeafRef<'_, T, B, C>> {
         self.tree()
             .is_not_empty()
             .then(|| unsafe { LeafRef::new(self.leaf.assume_init()) })
     }
 
-    fn tree(&self) -> &BVec<T> {
+    fn tree(&self) -> &BVec<T, B, C> {
         unsafe { self.tree.as_ref() }
     }
 
@@ -412,16 +818,22 @@ where
     }
 }
 
-impl<'a, T> CursorInner<'a, ownership::Immut<'a>, T> {
+impl<'a, T, const B: usize, const C: usize> CursorInner<'a, ownership::Immut<'a>, T, B, C> {
     #[must_use]
     pub unsafe fn get_unchecked(&self) -> &'a T {
         unsafe { LeafRef::new(self.leaf.assume_init()).value_unchecked(self.leaf_index) }
     }
+
+    /// Returns the elements from the cursor to the end of its leaf.
+    #[must_use]
+    pub unsafe fn leaf_tail_unchecked(&self) -> &'a [T] {
+        unsafe { &LeafRef::new(self.leaf.assume_init()).values()[self.leaf_index..] }
+    }
 }
 
-impl<'a, T> CursorInner<'a, ownership::Mut<'a>, T> {
+impl<'a, T, const B: usize, const C: usize> CursorInner<'a, ownership::Mut<'a>, T, B, C> {
     // TODO: this should not be unbounded?
-    fn leaf_mut<'b>(&mut self) -> Option<LeafMut<'b, T>>
+    fn leaf_mut<'b>(&mut self) -> Option<LeafMut<'b, T, B, C>>
     where
         T: 'b,
     {
@@ -430,10 +842,31 @@ impl<'a, T> CursorInner<'a, ownership::Mut<'a>, T> {
             .then(|| unsafe { LeafMut::new(self.leaf.assume_init()) })
     }
 
-    fn root_mut(&mut self) -> &mut MaybeUninit<NodePtr<T>> {
+    fn root_mut(&mut self) -> &mut MaybeUninit<NodePtr<T, B, C>> {
         unsafe { &mut self.tree.as_mut().root }
     }
 
+    /// Returns another mutable cursor at the same position. The cursors must
+    /// not be used to access the same elements or to change the tree.
+    pub unsafe fn duplicate(&self) -> Self {
+        Self {
+            tree: self.tree,
+            leaf: self.leaf,
+            leaf_index: self.leaf_index,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a shared cursor at the same position.
+    pub unsafe fn reborrow(&self) -> CursorInner<'_, ownership::Immut<'_>, T, B, C> {
+        CursorInner {
+            tree: self.tree,
+            leaf: self.leaf,
+            leaf_index: self.leaf_index,
+            _marker: PhantomData,
+        }
+    }
+
     #[must_use]
     pub unsafe fn get_unchecked(&self) -> &T {
         unsafe { LeafRef::new(self.leaf.assume_init()).value_unchecked(self.leaf_index) }
@@ -449,7 +882,7 @@ impl<'a, T> CursorInner<'a, ownership::Mut<'a>, T> {
         unsafe { LeafMut::new(self.leaf.assume_init()).into_value_unchecked_mut(self.leaf_index) }
     }
 
-    unsafe fn add_path_lengths_wrapping(&mut self, amount: usize) -> bool {
+    unsafe fn add_path_lengths_wrapping(&mut self, amount: usize) {
         unsafe {
             let mut new_parent = self.leaf_mut().and_then(Node::into_parent_and_index2);
 
@@ -460,7 +893,6 @@ impl<'a, T> CursorInner<'a, ownership::Mut<'a>, T> {
 
             let tree = self.tree.as_mut();
             tree.len = tree.len.wrapping_add(amount);
-            tree.len > isize::MAX as usize
         }
     }
 
@@ -471,7 +903,7 @@ impl<'a, T> CursorInner<'a, ownership::Mut<'a>, T> {
         self.leaf.write(new_root);
     }
 
-    unsafe fn split_root(&mut self, new_node: RawNodeWithLen<T>) {
+    unsafe fn split_root(&mut self, new_node: RawNodeWithLen<T, B, C>) {
         let old_root = self.tree().root().unwrap();
         let old_root_len = self.tree().len() - new_node.0;
         self.root_mut().write(InternalNode::from_child_array([
@@ -480,13 +912,16 @@ impl<'a, T> CursorInner<'a, ownership::Mut<'a>, T> {
         ]));
     }
 
+    /// # Panics
+    /// Panics if the length of the tree would exceed `isize::MAX`. The tree
+    /// is left unchanged in that case.
     pub fn insert(&mut self, value: T) {
-        let maybe_leaf = self.leaf_mut();
-
-        if unsafe { self.add_path_lengths_wrapping(1) } {
-            unsafe { self.tree.as_mut().len = 0 };
+        if self.len() == isize::MAX as usize {
             panic_length_overflow();
-        };
+        }
+
+        let maybe_leaf = self.leaf_mut();
+        unsafe { self.add_path_lengths_wrapping(1) };
 
         let leaf_index = self.leaf_index;
         let Some(mut leaf) = maybe_leaf else {
@@ -587,22 +1022,27 @@ impl<'a, T> CursorInner<'a, ownership::Mut<'a>, T> {
     }
 }
 
-pub struct InboundsCursor<'a, T> {
-    inner: CursorInner<'a, ownership::Immut<'a>, T>,
+pub struct InboundsCursor<
+    'a,
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    inner: CursorInner<'a, ownership::Immut<'a>, T, B, C>,
 }
 
-impl<'a, T> InboundsCursor<'a, T> {
-    pub(crate) fn try_new(tree: &'a BVec<T>, index: usize) -> Option<Self> {
+impl<'a, T, const B: usize, const C: usize> InboundsCursor<'a, T, B, C> {
+    pub(crate) fn try_new(tree: &'a BVec<T, B, C>, index: usize) -> Option<Self> {
         CursorInner::try_new_inbounds(tree, index).map(|inner| Self { inner })
     }
 
-    pub(crate) fn try_new_first(tree: &'a BVec<T>) -> Option<Self> {
+    pub(crate) fn try_new_first(tree: &'a BVec<T, B, C>) -> Option<Self> {
         Some(Self {
             inner: CursorInner::try_new_inbounds_first(tree)?,
         })
     }
 
-    pub(crate) fn try_new_last(tree: &'a BVec<T>) -> Option<Self> {
+    pub(crate) fn try_new_last(tree: &'a BVec<T, B, C>) -> Option<Self> {
         tree.is_not_empty().then(|| unsafe {
             Self {
                 inner: CursorInner::new_last_unchecked(tree),
@@ -611,29 +1051,34 @@ impl<'a, T> InboundsCursor<'a, T> {
     }
 }
 
-impl<'a, T> InboundsCursor<'a, T> {
+impl<'a, T, const B: usize, const C: usize> InboundsCursor<'a, T, B, C> {
     #[must_use]
     pub fn get(self) -> &'a T {
         unsafe { self.inner.get_unchecked() }
     }
 }
 
-pub struct InboundsCursorMut<'a, T> {
-    inner: CursorInner<'a, ownership::Mut<'a>, T>,
+pub struct InboundsCursorMut<
+    'a,
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    inner: CursorInner<'a, ownership::Mut<'a>, T, B, C>,
 }
 
-impl<'a, T> InboundsCursorMut<'a, T> {
-    pub(crate) fn try_new(tree: &'a mut BVec<T>, index: usize) -> Option<Self> {
+impl<'a, T, const B: usize, const C: usize> InboundsCursorMut<'a, T, B, C> {
+    pub(crate) fn try_new(tree: &'a mut BVec<T, B, C>, index: usize) -> Option<Self> {
         CursorInner::try_new_inbounds(tree, index).map(|inner| Self { inner })
     }
 
-    pub(crate) fn try_new_first(tree: &'a mut BVec<T>) -> Option<Self> {
+    pub(crate) fn try_new_first(tree: &'a mut BVec<T, B, C>) -> Option<Self> {
         Some(Self {
             inner: CursorInner::try_new_inbounds_first(tree)?,
         })
     }
 
-    pub(crate) fn try_new_last(tree: &'a mut BVec<T>) -> Option<Self> {
+    pub(crate) fn try_new_last(tree: &'a mut BVec<T, B, C>) -> Option<Self> {
         tree.is_not_empty().then(|| unsafe {
             Self {
                 inner: CursorInner::new_last_unchecked(tree),
@@ -642,7 +1087,7 @@ impl<'a, T> InboundsCursorMut<'a, T> {
     }
 }
 
-impl<'a, T> InboundsCursorMut<'a, T> {
+impl<'a, T, const B: usize, const C: usize> InboundsCursorMut<'a, T, B, C> {
     pub fn into_mut(self) -> &'a mut T {
         unsafe { self.inner.into_unchecked_mut() }
     }
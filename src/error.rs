@@ -0,0 +1,25 @@
+use core::fmt;
+
+/// The error returned by [`BVec::try_get`](crate::BVec::try_get) and
+/// [`BVec::try_get_mut`](crate::BVec::try_get_mut) when the index is out of
+/// bounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IndexError {
+    /// The index that was out of bounds.
+    pub index: usize,
+    /// The length of the sequence at the time of the access.
+    pub len: usize,
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index out of bounds: the len is {} but the index is {}",
+            self.len, self.index
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IndexError {}
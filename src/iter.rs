@@ -1,28 +1,135 @@
 //! Iterator `struct`s for `BVec`.
 
-// TODO: impl FusedIterator
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem,
+    num::NonZeroUsize,
+    ops::{ControlFlow, RangeBounds},
+};
 
-use core::{iter::FusedIterator, ops::Bound, ops::RangeBounds};
+use crate::{
+    cursor::CursorInner, ownership, utils::resolve_range, BVec, CursorMut, DEFAULT_BRANCH_FACTOR,
+    DEFAULT_LEAF_BYTES,
+};
 
-use crate::{cursor::CursorInner, ownership, BVec, CursorMut};
-
-#[derive(Clone)]
-pub struct Iter<'a, T> {
-    cursor: CursorInner<'a, ownership::Immut<'a>, T>,
+pub struct Iter<'a, T, const B: usize = DEFAULT_BRANCH_FACTOR, const C: usize = DEFAULT_LEAF_BYTES>
+{
+    cursor: CursorInner<'a, ownership::Immut<'a>, T, B, C>,
+    /// A cursor at the last remaining element. It is only created by the
+    /// first call to `next_back`, so that iterating forwards doesn't pay for
+    /// finding the end.
+    back: Option<CursorInner<'a, ownership::Immut<'a>, T, B, C>>,
     remaining_count: usize,
 }
 
-impl<'a, T> Iter<'a, T> {
+impl<'a, T, const B: usize, const C: usize> Clone for Iter<'a, T, B, C> {
+    fn clone(&self) -> Self {
+        Self {
+            cursor: self.cursor.clone(),
+            back: self.back.clone(),
+            remaining_count: self.remaining_count,
+        }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Iter<'a, T, B, C> {
     #[must_use]
-    pub(crate) unsafe fn new(v: &'a BVec<T>, start: usize, end: usize) -> Self {
+    pub(crate) unsafe fn new(v: &'a BVec<T, B, C>, start: usize, end: usize) -> Self {
         Self {
             cursor: CursorInner::new(v, start),
+            back: None,
             remaining_count: end - start,
         }
     }
+
+    /// Skips the next `n` elements by moving the cursor in O(log n) time.
+    ///
+    /// Like the unstable `Iterator::advance_by`, this returns `Err(k)` with
+    /// `k` being the number of elements that were missing if there were
+    /// fewer than `n` left. The iterator is empty afterwards in that case.
+    pub fn advance_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
+        if n >= self.remaining_count {
+            let missing = n - self.remaining_count;
+            self.remaining_count = 0;
+            return NonZeroUsize::new(missing).map_or(Ok(()), Err);
+        }
+        self.remaining_count -= n;
+        unsafe { self.cursor.move_inbounds_unchecked(n as isize) };
+        Ok(())
+    }
+
+    /// Drops the last `n` elements from the iterator, see
+    /// [`advance_by`](Iter::advance_by).
+    pub fn advance_back_by(&mut self, n: usize) -> Result<(), NonZeroUsize> {
+        let missing = n.saturating_sub(self.remaining_count);
+        self.remaining_count -= n - missing;
+        if let Some(back) = &mut self.back {
+            if self.remaining_count != 0 {
+                unsafe { back.move_inbounds_unchecked(-(n as isize)) };
+            }
+        }
+        NonZeroUsize::new(missing).map_or(Ok(()), Err)
+    }
+
+    /// Folds the remaining elements with `f` until it breaks, like
+    /// `Iterator::try_fold`, which can't be overridden on stable.
+    ///
+    /// The elements are visited a leaf at a time as slices, so the cursor is
+    /// only moved once per leaf. If `f` breaks, the iterator is left right
+    /// after the element that caused it.
+    fn try_fold_leaves<Acc, R, F>(&mut self, mut acc: Acc, mut f: F) -> ControlFlow<R, Acc>
+    where
+        F: FnMut(Acc, &'a T) -> ControlFlow<R, Acc>,
+    {
+        while self.remaining_count > 0 {
+            let tail = unsafe { self.cursor.leaf_tail_unchecked() };
+            let run = &tail[..tail.len().min(self.remaining_count)];
+            for (i, value) in run.iter().enumerate() {
+                match f(acc, value) {
+                    ControlFlow::Continue(next) => acc = next,
+                    ControlFlow::Break(r) => {
+                        // `i + 1 <= self.remaining_count`, so this cannot fail.
+                        let _ = self.advance_by(i + 1);
+                        return ControlFlow::Break(r);
+                    }
+                }
+            }
+            self.remaining_count -= run.len();
+            if self.remaining_count != 0 {
+                // The run ends at the end of the leaf, so step to the next one.
+                self.cursor.leaf_index += run.len() - 1;
+                self.cursor.move_next_inbounds_unchecked();
+            }
+        }
+        ControlFlow::Continue(acc)
+    }
+
+    /// Splits the iterator into one over the first `mid` remaining elements
+    /// and one over the rest.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn split_at(self, mid: usize) -> (Self, Self) {
+        debug_assert!(mid <= self.remaining_count);
+        let mut cursor = self.cursor.clone();
+        if mid < self.remaining_count {
+            unsafe { cursor.move_inbounds_unchecked(mid as isize) };
+        }
+        let tail = Self {
+            cursor,
+            back: self.back.clone(),
+            remaining_count: self.remaining_count - mid,
+        };
+        let head = Self {
+            back: None,
+            remaining_count: mid,
+            ..self
+        };
+        (head, tail)
+    }
 }
 
-impl<'a, T> Iterator for Iter<'a, T> {
+impl<'a, T, const B: usize, const C: usize> Iterator for Iter<'a, T, B, C> {
     type Item = &'a T;
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -44,53 +151,302 @@ impl<'a, T> Iterator for Iter<'a, T> {
         self.remaining_count
     }
 
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.advance_by(n).ok()?;
+        self.next()
+    }
+
+    fn fold<Acc, F>(mut self, init: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let mut acc = init;
+        while self.remaining_count > 0 {
+            let tail = unsafe { self.cursor.leaf_tail_unchecked() };
+            let run = &tail[..tail.len().min(self.remaining_count)];
+            acc = run.iter().fold(acc, &mut f);
+            self.remaining_count -= run.len();
+            if self.remaining_count != 0 {
+                self.cursor.leaf_index += run.len() - 1;
+                self.cursor.move_next_inbounds_unchecked();
+            }
+        }
+        acc
+    }
+
+    fn for_each<F>(self, mut f: F)
+    where
+        F: FnMut(Self::Item),
+    {
+        self.fold((), |(), x| f(x));
+    }
+
+    fn find<P>(&mut self, mut predicate: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        match self.try_fold_leaves((), |(), x| {
+            if predicate(&x) {
+                ControlFlow::Break(x)
+            } else {
+                ControlFlow::Continue(())
+            }
+        }) {
+            ControlFlow::Break(found) => Some(found),
+            ControlFlow::Continue(()) => None,
+        }
+    }
+
+    fn find_map<U, F>(&mut self, mut f: F) -> Option<U>
+    where
+        F: FnMut(Self::Item) -> Option<U>,
+    {
+        match self.try_fold_leaves((), |(), x| {
+            f(x).map_or(ControlFlow::Continue(()), ControlFlow::Break)
+        }) {
+            ControlFlow::Break(found) => Some(found),
+            ControlFlow::Continue(()) => None,
+        }
+    }
+
+    fn position<P>(&mut self, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        match self.try_fold_leaves(0, |i, x| {
+            if predicate(x) {
+                ControlFlow::Break(i)
+            } else {
+                ControlFlow::Continue(i + 1)
+            }
+        }) {
+            ControlFlow::Break(i) => Some(i),
+            ControlFlow::Continue(_) => None,
+        }
+    }
+
+    fn any<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        self.find_map(|x| f(x).then_some(())).is_some()
+    }
+
+    fn all<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        self.find_map(|x| (!f(x)).then_some(())).is_none()
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> DoubleEndedIterator for Iter<'a, T, B, C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.remaining_count > 0).then(|| {
+            let back = self.back.get_or_insert_with(|| {
+                let mut back = self.cursor.clone();
+                unsafe { back.move_inbounds_unchecked(self.remaining_count as isize - 1) };
+                back
+            });
+            let ret = unsafe { back.get_unchecked() };
+            self.remaining_count -= 1;
+            if self.remaining_count != 0 {
+                unsafe { back.move_prev_inbounds_unchecked() };
+            }
+            ret
+        })
+    }
+}
+
+// SAFETY: like `slice::Iter`, this only gives out shared references to the
+// elements, and it only reads the nodes.
+unsafe impl<'a, T: Sync, const B: usize, const C: usize> Send for Iter<'a, T, B, C> {}
+
+impl<'a, T, const B: usize, const C: usize> ExactSizeIterator for Iter<'a, T, B, C> {}
+impl<'a, T, const B: usize, const C: usize> FusedIterator for Iter<'a, T, B, C> {}
+
+pub struct IterMut<
+    'a,
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    cursor: CursorInner<'a, ownership::Mut<'a>, T, B, C>,
+    remaining_count: usize,
+    _invariant: PhantomData<&'a mut T>,
+}
+
+impl<'a, T, const B: usize, const C: usize> IterMut<'a, T, B, C> {
+    #[must_use]
+    pub(crate) unsafe fn new(v: &'a mut BVec<T, B, C>, start: usize, end: usize) -> Self {
+        Self {
+            cursor: CursorInner::new(v, start),
+            remaining_count: end - start,
+            _invariant: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> IterMut<'a, T, B, C> {
+    /// Splits the iterator into one over the first `mid` remaining elements
+    /// and one over the rest.
+    pub(crate) fn split_at(self, mid: usize) -> (Self, Self) {
+        debug_assert!(mid <= self.remaining_count);
+        // SAFETY: the iterators visit disjoint elements and do not change the tree.
+        let mut cursor = unsafe { self.cursor.duplicate() };
+        if mid < self.remaining_count {
+            unsafe { cursor.move_inbounds_unchecked(mid as isize) };
+        }
+        let tail = Self {
+            cursor,
+            remaining_count: self.remaining_count - mid,
+            _invariant: PhantomData,
+        };
+        let head = Self {
+            remaining_count: mid,
+            ..self
+        };
+        (head, tail)
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Iterator for IterMut<'a, T, B, C> {
+    type Item = &'a mut T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.remaining_count > 0).then(|| {
+            // SAFETY: every slot is yielded at most once, so the returned
+            // references never alias.
+            let ret = unsafe { &mut *(self.cursor.get_unchecked_mut() as *mut T) };
+            self.remaining_count -= 1;
+            if self.remaining_count != 0 {
+                self.cursor.move_next_inbounds_unchecked();
+            }
+            ret
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining_count, Some(self.remaining_count))
+    }
+
+    fn count(self) -> usize {
+        self.remaining_count
+    }
+
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
         if n >= self.remaining_count {
             self.remaining_count = 0;
             None
         } else {
+            self.remaining_count -= n;
             unsafe { self.cursor.move_inbounds_unchecked(n as isize) };
-            self.remaining_count -= n + 1;
-            unsafe { Some(self.cursor.get_unchecked()) }
+            self.next()
         }
     }
+}
+
+impl<'a, T, const B: usize, const C: usize> ExactSizeIterator for IterMut<'a, T, B, C> {}
+impl<'a, T, const B: usize, const C: usize> FusedIterator for IterMut<'a, T, B, C> {}
+
+/// An iterator over the contiguous runs of elements stored in the leaves of
+/// a `BVec`, from front to back.
+pub(crate) struct LeafSlices<'a, T, const B: usize, const C: usize> {
+    cursor: CursorInner<'a, ownership::Immut<'a>, T, B, C>,
+    remaining_count: usize,
+}
+
+impl<'a, T, const B: usize, const C: usize> LeafSlices<'a, T, B, C> {
+    pub(crate) fn new(v: &'a BVec<T, B, C>) -> Self {
+        Self {
+            cursor: CursorInner::new(v, 0),
+            remaining_count: v.len(),
+        }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Iterator for LeafSlices<'a, T, B, C> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.remaining_count > 0).then(|| {
+            let slice = unsafe { self.cursor.leaf_tail_unchecked() };
+            self.remaining_count -= slice.len();
+            if self.remaining_count != 0 {
+                unsafe { self.cursor.move_inbounds_unchecked(slice.len() as isize) };
+            }
+            slice
+        })
+    }
+}
+
+/// An iterator that moves the elements out of a `BVec`, created by its
+/// [`IntoIterator`] implementation.
+///
+/// The elements are removed from the tree as they are yielded, and the tree
+/// is rebalanced like after any removal. Nodes are freed as soon as their
+/// elements have been merged into their neighbours, so the memory in use
+/// shrinks along with the number of remaining elements instead of staying
+/// allocated until the iterator is dropped.
+pub struct IntoIter<T, const B: usize = DEFAULT_BRANCH_FACTOR, const C: usize = DEFAULT_LEAF_BYTES>
+{
+    tree: BVec<T, B, C>,
+}
+
+impl<T, const B: usize, const C: usize> IntoIter<T, B, C> {
+    #[must_use]
+    pub(crate) fn new(tree: BVec<T, B, C>) -> Self {
+        Self { tree }
+    }
 
-    // TODO: advance_by
+    /// Returns the elements that have not been yielded yet.
+    #[must_use]
+    pub fn as_bvec(&self) -> &BVec<T, B, C> {
+        &self.tree
+    }
 }
 
-impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
-impl<'a, T> FusedIterator for Iter<'a, T> {}
+impl<T, const B: usize, const C: usize> Iterator for IntoIter<T, B, C> {
+    type Item = T;
 
-pub struct Drain<'a, T> {
-    cursor: CursorMut<'a, T>,
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tree.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.tree.len(), Some(self.tree.len()))
+    }
+}
+
+impl<T, const B: usize, const C: usize> DoubleEndedIterator for IntoIter<T, B, C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.tree.pop_back()
+    }
+}
+
+impl<T, const B: usize, const C: usize> ExactSizeIterator for IntoIter<T, B, C> {}
+impl<T, const B: usize, const C: usize> FusedIterator for IntoIter<T, B, C> {}
+
+pub struct Drain<'a, T, const B: usize = DEFAULT_BRANCH_FACTOR, const C: usize = DEFAULT_LEAF_BYTES>
+{
+    cursor: CursorMut<'a, T, B, C>,
     remaining_count: usize,
 }
 
-impl<'a, T> Drain<'a, T> {
-    pub(crate) fn new<R>(t: &'a mut BVec<T>, range: R) -> Self
+impl<'a, T, const B: usize, const C: usize> Drain<'a, T, B, C> {
+    pub(crate) fn new<R>(t: &'a mut BVec<T, B, C>, range: R) -> Self
     where
         R: RangeBounds<usize>,
     {
-        let start = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Included(s) => *s,
-            Bound::Excluded(s) => s + 1,
-        };
-        let end = match range.end_bound() {
-            Bound::Unbounded => t.len(),
-            Bound::Included(s) => s + 1,
-            Bound::Excluded(s) => *s,
-        };
-        assert!(end <= t.len());
-        // TODO: start out of bounds
+        let (start, end) = resolve_range(&range, t.len());
         Self {
             cursor: t.cursor_at_mut(start),
-            remaining_count: end.saturating_sub(start),
+            remaining_count: end - start,
         }
     }
 }
 
-impl<'a, T> Iterator for Drain<'a, T> {
+impl<'a, T, const B: usize, const C: usize> Iterator for Drain<'a, T, B, C> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -99,10 +455,369 @@ impl<'a, T> Iterator for Drain<'a, T> {
             self.cursor.remove()
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining_count, Some(self.remaining_count))
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> DoubleEndedIterator for Drain<'a, T, B, C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.remaining_count > 0).then(|| {
+            self.remaining_count -= 1;
+            // The cursor stays at the start of the range for `next`.
+            let offset = self.remaining_count as isize;
+            self.cursor.move_(offset);
+            let value = self.cursor.remove();
+            self.cursor.move_(-offset);
+            value
+        })
+    }
 }
 
-impl<'a, T> Drop for Drain<'a, T> {
+impl<'a, T, const B: usize, const C: usize> ExactSizeIterator for Drain<'a, T, B, C> {}
+
+impl<'a, T, const B: usize, const C: usize> Drop for Drain<'a, T, B, C> {
     fn drop(&mut self) {
-        for _ in self {}
+        /// Keeps removing the rest of the range if dropping an element panics.
+        struct DropGuard<'r, 'a, T, const B: usize, const C: usize>(&'r mut Drain<'a, T, B, C>);
+
+        impl<'r, 'a, T, const B: usize, const C: usize> Drop for DropGuard<'r, 'a, T, B, C> {
+            fn drop(&mut self) {
+                self.0.for_each(drop);
+            }
+        }
+
+        while let Some(value) = self.next() {
+            let guard = DropGuard(self);
+            drop(value);
+            mem::forget(guard);
+        }
+    }
+}
+
+/// An iterator that replaces a range of a `BVec`, created by
+/// [`BVec::splice`]. The removed elements are yielded, and the replacement
+/// is inserted when the iterator is dropped.
+pub struct Splice<
+    'a,
+    I: Iterator,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    drain: Drain<'a, I::Item, B, C>,
+    replace_with: I,
+}
+
+impl<'a, I: Iterator, const B: usize, const C: usize> Splice<'a, I, B, C> {
+    pub(crate) fn new<R>(t: &'a mut BVec<I::Item, B, C>, range: R, replace_with: I) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        Self {
+            drain: Drain::new(t, range),
+            replace_with,
+        }
+    }
+}
+
+impl<'a, I: Iterator, const B: usize, const C: usize> Iterator for Splice<'a, I, B, C> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
     }
 }
+
+impl<'a, I: Iterator, const B: usize, const C: usize> Drop for Splice<'a, I, B, C> {
+    fn drop(&mut self) {
+        self.drain.by_ref().for_each(drop);
+
+        // The drained range is now empty and the cursor points at its start.
+        let cursor = &mut self.drain.cursor;
+        for value in self.replace_with.by_ref() {
+            cursor.insert(value);
+            cursor.move_next();
+        }
+    }
+}
+
+/// An iterator that removes and yields the elements of a `BVec` that match
+/// a predicate, created by [`BVec::extract_if`]. The elements that are not
+/// yielded before the iterator is dropped are still checked, and the matching
+/// ones are dropped.
+pub struct ExtractIf<
+    'a,
+    T,
+    F: FnMut(&mut T) -> bool,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    cursor: CursorMut<'a, T, B, C>,
+    pred: F,
+    /// Set while `pred` runs, so that `Drop` doesn't call it again after
+    /// it panicked.
+    panic_flag: bool,
+}
+
+impl<'a, T, F: FnMut(&mut T) -> bool, const B: usize, const C: usize> ExtractIf<'a, T, F, B, C> {
+    pub(crate) fn new(t: &'a mut BVec<T, B, C>, pred: F) -> Self {
+        Self {
+            cursor: t.cursor_front_mut(),
+            pred,
+            panic_flag: false,
+        }
+    }
+}
+
+impl<'a, T, F: FnMut(&mut T) -> bool, const B: usize, const C: usize> Iterator
+    for ExtractIf<'a, T, F, B, C>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(value) = self.cursor.get_mut() {
+            self.panic_flag = true;
+            let extract = (self.pred)(value);
+            self.panic_flag = false;
+            if extract {
+                return Some(self.cursor.remove());
+            }
+            self.cursor.move_next();
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.cursor.remaining()))
+    }
+}
+
+impl<'a, T, F: FnMut(&mut T) -> bool, const B: usize, const C: usize> Drop
+    for ExtractIf<'a, T, F, B, C>
+{
+    fn drop(&mut self) {
+        if !self.panic_flag {
+            self.for_each(drop);
+        }
+    }
+}
+
+/// An iterator over non-overlapping chunks of a `BVec`, created by
+/// [`BVec::chunks`]. The last chunk is shorter if the length is not
+/// divisible by the chunk size.
+pub struct Chunks<
+    'a,
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    iter: Iter<'a, T, B, C>,
+    chunk_size: usize,
+}
+
+impl<'a, T, const B: usize, const C: usize> Chunks<'a, T, B, C> {
+    pub(crate) fn new(tree: &'a BVec<T, B, C>, chunk_size: usize) -> Self {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        Self {
+            iter: tree.iter(),
+            chunk_size,
+        }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Clone for Chunks<'a, T, B, C> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Iterator for Chunks<'a, T, B, C> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.iter.len().min(self.chunk_size);
+        (len > 0).then(|| {
+            let mut chunk = Vec::with_capacity(len);
+            chunk.extend(self.iter.by_ref().take(len));
+            chunk
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.len().div_ceil(self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> ExactSizeIterator for Chunks<'a, T, B, C> {}
+impl<'a, T, const B: usize, const C: usize> FusedIterator for Chunks<'a, T, B, C> {}
+
+/// An iterator over non-overlapping chunks of mutable references into a
+/// `BVec`, created by [`BVec::chunks_mut`]. The last chunk is shorter if the
+/// length is not divisible by the chunk size.
+pub struct ChunksMut<
+    'a,
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    iter: IterMut<'a, T, B, C>,
+    chunk_size: usize,
+}
+
+impl<'a, T, const B: usize, const C: usize> ChunksMut<'a, T, B, C> {
+    pub(crate) fn new(tree: &'a mut BVec<T, B, C>, chunk_size: usize) -> Self {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        Self {
+            iter: tree.iter_mut(),
+            chunk_size,
+        }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Iterator for ChunksMut<'a, T, B, C> {
+    type Item = Vec<&'a mut T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.iter.len().min(self.chunk_size);
+        (len > 0).then(|| {
+            // The chunks don't alias because `IterMut` yields every element once.
+            let mut chunk = Vec::with_capacity(len);
+            chunk.extend(self.iter.by_ref().take(len));
+            chunk
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.len().div_ceil(self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> ExactSizeIterator for ChunksMut<'a, T, B, C> {}
+impl<'a, T, const B: usize, const C: usize> FusedIterator for ChunksMut<'a, T, B, C> {}
+
+/// An iterator over non-overlapping chunks of a `BVec` starting from the
+/// end, created by [`BVec::rchunks`]. The last chunk is shorter if the
+/// length is not divisible by the chunk size.
+pub struct RChunks<
+    'a,
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    tree: &'a BVec<T, B, C>,
+    end: usize,
+    chunk_size: usize,
+}
+
+impl<'a, T, const B: usize, const C: usize> RChunks<'a, T, B, C> {
+    pub(crate) fn new(tree: &'a BVec<T, B, C>, chunk_size: usize) -> Self {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        Self {
+            tree,
+            end: tree.len(),
+            chunk_size,
+        }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Clone for RChunks<'a, T, B, C> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Iterator for RChunks<'a, T, B, C> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.end > 0).then(|| {
+            let start = self.end.saturating_sub(self.chunk_size);
+            let chunk = unsafe { Iter::new(self.tree, start, self.end) }.collect();
+            self.end = start;
+            chunk
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end.div_ceil(self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> ExactSizeIterator for RChunks<'a, T, B, C> {}
+impl<'a, T, const B: usize, const C: usize> FusedIterator for RChunks<'a, T, B, C> {}
+
+/// An iterator over overlapping windows of a `BVec`, created by
+/// [`BVec::windows`].
+pub struct Windows<
+    'a,
+    T,
+    const B: usize = DEFAULT_BRANCH_FACTOR,
+    const C: usize = DEFAULT_LEAF_BYTES,
+> {
+    iter: Iter<'a, T, B, C>,
+    window: VecDeque<&'a T>,
+    size: usize,
+}
+
+impl<'a, T, const B: usize, const C: usize> Windows<'a, T, B, C> {
+    pub(crate) fn new(tree: &'a BVec<T, B, C>, size: usize) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        Self {
+            iter: tree.iter(),
+            window: VecDeque::new(),
+            size,
+        }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Clone for Windows<'a, T, B, C> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            window: self.window.clone(),
+            size: self.size,
+        }
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> Iterator for Windows<'a, T, B, C> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() < self.size {
+            if self.iter.len() < self.size {
+                return None;
+            }
+            self.window.reserve_exact(self.size);
+            self.window.extend(self.iter.by_ref().take(self.size));
+        } else {
+            let next = self.iter.next()?;
+            self.window.pop_front();
+            self.window.push_back(next);
+        }
+        Some(self.window.iter().copied().collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.window.len() < self.size {
+            (self.iter.len() + 1).saturating_sub(self.size)
+        } else {
+            self.iter.len()
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> ExactSizeIterator for Windows<'a, T, B, C> {}
+impl<'a, T, const B: usize, const C: usize> FusedIterator for Windows<'a, T, B, C> {}
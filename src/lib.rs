@@ -4,34 +4,90 @@
 // TODO #![deny(missing_docs)]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+use alloc::{collections::VecDeque, vec::Vec};
 use core::{
+    cmp::Ordering,
     fmt,
     hash::{Hash, Hasher},
-    mem::MaybeUninit,
+    iter::{Product, Sum},
+    mem::{self, MaybeUninit},
     ops::{Index, IndexMut, RangeBounds},
+    ptr,
 };
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod builder;
 mod cursor;
+mod error;
+#[cfg(feature = "std")]
+mod io;
 pub mod iter;
+// The unsafe traits marking node heights and ownership are implemented
+// only inside the crate.
+#[allow(clippy::missing_safety_doc)]
 mod node;
+#[allow(clippy::missing_safety_doc)]
 mod ownership;
 mod panics;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "serde")]
+mod serde;
 mod utils;
 
+pub use builder::BVecBuilder;
 use cursor::CursorInner;
 pub use cursor::{Cursor, CursorMut, InboundsCursor, InboundsCursorMut};
+pub use error::IndexError;
+#[cfg(feature = "rayon")]
+pub use rayon::ParIter;
 
-use iter::{Drain, Iter};
-use node::NodePtr;
-use panics::panic_out_of_bounds;
+use iter::{
+    Chunks, ChunksMut, Drain, ExtractIf, IntoIter, Iter, IterMut, LeafSlices, RChunks, Splice,
+    Windows,
+};
+use node::{
+    free::{free_tree, free_tree_with},
+    handle::InternalRef,
+    join::join,
+    reverse::reverse,
+    search::partition_point,
+    split::split,
+    AssertParams, NodeBase, NodePtr, RawNodeWithLen,
+};
+use panics::{panic_length_overflow, panic_out_of_bounds};
+use utils::resolve_range;
 
 //pub fn foo<'a>(b: &'a mut BVec<i32>, x: usize)-> alloc::vec::Vec<i32> {
 //    b.iter().copied().collect()
 //}
 
-pub struct BVec<T> {
-    root: MaybeUninit<NodePtr<T>>,
+// Under Miri the defaults are small, so that the tests using them build
+// trees with several levels and exercise the unsafe node code.
+
+/// The default branch factor of the internal nodes of a [`BVec`].
+#[cfg(not(miri))]
+pub const DEFAULT_BRANCH_FACTOR: usize = 32;
+#[cfg(miri)]
+pub const DEFAULT_BRANCH_FACTOR: usize = 4;
+
+/// The default size in bytes of the value arrays in the leaves of a [`BVec`].
+#[cfg(not(miri))]
+pub const DEFAULT_LEAF_BYTES: usize = 256;
+#[cfg(miri)]
+pub const DEFAULT_LEAF_BYTES: usize = 16;
+
+/// A sequence backed by a B-tree, with logarithmic indexing, insertion and removal.
+///
+/// Internal nodes have up to `B` children, which must be a power of two
+/// between 4 and 128. Leaves store up to `C` bytes worth of values, but
+/// always at least one value.
+pub struct BVec<T, const B: usize = DEFAULT_BRANCH_FACTOR, const C: usize = DEFAULT_LEAF_BYTES> {
+    root: MaybeUninit<NodePtr<T, B, C>>,
     len: usize,
 }
 
@@ -39,12 +95,55 @@ impl<T> BVec<T> {
     #[must_use]
     #[inline]
     pub const fn new() -> Self {
+        Self::new_with_params()
+    }
+}
+
+impl<T, const B: usize, const C: usize> BVec<T, B, C> {
+    /// Like [`new`](BVec::new), but for any node sizes.
+    ///
+    /// # Panics
+    /// Fails to compile if `B` or `C` is invalid, see [`BVec`].
+    #[must_use]
+    #[inline]
+    pub const fn new_with_params() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = AssertParams::<B, C>::OK;
         Self {
             root: MaybeUninit::uninit(),
             len: 0,
         }
     }
 
+    /// Creates a sequence of `len` clones of `default`, except at the
+    /// positions given by `edits`, which hold the accompanying values instead.
+    ///
+    /// The tree is built directly from the leaves up, which is much cheaper
+    /// than filling it with defaults and overwriting or inserting the edits.
+    ///
+    /// The indices of `edits` must be strictly increasing and less than `len`.
+    pub fn from_sparse<I>(len: usize, default: T, edits: I) -> Self
+    where
+        T: Clone,
+        I: IntoIterator<Item = (usize, T)>,
+    {
+        let mut edits = edits.into_iter().peekable();
+        let mut builder = BVecBuilder::new_with_params();
+        for index in 0..len {
+            if let Some((_, value)) = edits.next_if(|&(i, _)| i == index) {
+                debug_assert!(
+                    edits.peek().is_none_or(|&(next, _)| next > index),
+                    "edits are not sorted"
+                );
+                builder.push(value);
+            } else {
+                builder.push(default.clone());
+            }
+        }
+        debug_assert!(edits.next().is_none(), "edit index out of bounds");
+        builder.finish()
+    }
+
     #[must_use]
     #[inline]
     pub fn len(&self) -> usize {
@@ -62,7 +161,7 @@ impl<T> BVec<T> {
         self.len != 0
     }
 
-    fn root(&self) -> Option<NodePtr<T>> {
+    fn root(&self) -> Option<NodePtr<T, B, C>> {
         self.is_not_empty()
             .then(|| unsafe { self.root.assume_init() })
     }
@@ -77,6 +176,107 @@ impl<T> BVec<T> {
         InboundsCursorMut::try_new(self, index).map(InboundsCursorMut::into_mut)
     }
 
+    /// Returns a reference to the element at `index`, or an [`IndexError`]
+    /// if it is out of bounds.
+    pub fn try_get(&self, index: usize) -> Result<&T, IndexError> {
+        let len = self.len();
+        InboundsCursor::try_new(self, index)
+            .map(InboundsCursor::get)
+            .ok_or(IndexError { index, len })
+    }
+
+    /// Returns a mutable reference to the element at `index`, or an
+    /// [`IndexError`] if it is out of bounds.
+    pub fn try_get_mut(&mut self, index: usize) -> Result<&mut T, IndexError> {
+        let len = self.len();
+        InboundsCursorMut::try_new(self, index)
+            .map(InboundsCursorMut::into_mut)
+            .ok_or(IndexError { index, len })
+    }
+
+    /// Returns references to the elements at `sorted_indices`, or `None` if
+    /// any of the indices is out of bounds.
+    ///
+    /// The indices must be strictly increasing. They are visited by a single
+    /// cursor moving forward instead of descending from the root for each one.
+    #[must_use]
+    pub fn get_sorted(&self, sorted_indices: &[usize]) -> Option<Vec<&T>> {
+        debug_assert!(
+            sorted_indices.windows(2).all(|w| w[0] < w[1]),
+            "indices are not sorted"
+        );
+        if sorted_indices.iter().any(|&index| index >= self.len()) {
+            return None;
+        }
+
+        let Some(&first) = sorted_indices.first() else {
+            return Some(Vec::new());
+        };
+        let mut cursor = CursorInner::<ownership::Immut, T, B, C>::try_new_inbounds(self, first)?;
+        let mut prev = first;
+        let mut refs = Vec::with_capacity(sorted_indices.len());
+        for &index in sorted_indices {
+            unsafe {
+                cursor.move_inbounds_unchecked(index.wrapping_sub(prev) as isize);
+                refs.push(cursor.get_unchecked());
+            }
+            prev = index;
+        }
+        Some(refs)
+    }
+
+    /// Returns the longest contiguous slice of elements starting at `index`,
+    /// or `None` if `index` is out of bounds.
+    ///
+    /// The slice ends at the end of the leaf containing `index`, so it never
+    /// spans more than one leaf. The rest of the sequence can be visited by
+    /// calling this again with `index + slice.len()`.
+    #[must_use]
+    pub fn contiguous_run(&self, index: usize) -> Option<&[T]> {
+        CursorInner::<ownership::Immut, T, B, C>::try_new_inbounds(self, index)
+            .map(|cursor| unsafe { cursor.leaf_tail_unchecked() })
+    }
+
+    /// Returns mutable references to the elements at `indices`, or `None` if
+    /// any of the indices is out of bounds or if two of them are equal.
+    #[must_use]
+    pub fn get_many_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+        for (i, &index) in indices.iter().enumerate() {
+            if index >= self.len() || indices[..i].contains(&index) {
+                return None;
+            }
+        }
+
+        let ptrs = indices.map(|index| -> *mut T {
+            unsafe { InboundsCursorMut::try_new(self, index).unwrap_unchecked() }.into_mut()
+        });
+        // SAFETY: the indices are distinct, so the pointers point to distinct elements of the tree.
+        Some(ptrs.map(|ptr| unsafe { &mut *ptr }))
+    }
+
+    /// Returns a mutable reference to the element at `index`, first growing
+    /// the sequence up to and including `index` if it is out of bounds.
+    ///
+    /// Each new element is produced by a separate call to `f`, in order from
+    /// front to back, like [`Vec::resize_with`]. The new elements are joined
+    /// onto the tree at once, see [`append_from_iter`](BVec::append_from_iter).
+    ///
+    /// # Panics
+    /// Panics if the new length would overflow.
+    pub fn get_or_insert_with<F>(&mut self, index: usize, f: F) -> &mut T
+    where
+        F: FnMut() -> T,
+    {
+        let len = self.len();
+        if index >= len {
+            if index >= isize::MAX as usize {
+                panic_length_overflow();
+            }
+            self.append_from_iter(core::iter::repeat_with(f).take(index - len + 1));
+        }
+        unsafe { self.get_mut(index).unwrap_unchecked() }
+    }
+
     #[must_use]
     pub fn first(&self) -> Option<&T> {
         InboundsCursor::try_new_first(self).map(InboundsCursor::get)
@@ -97,6 +297,33 @@ impl<T> BVec<T> {
         InboundsCursorMut::try_new_last(self).map(InboundsCursorMut::into_mut)
     }
 
+    /// Returns the first element and a cursor at the start of the rest of
+    /// the sequence, which is past the end if there is no rest. Returns `None`
+    /// if the sequence is empty.
+    #[must_use]
+    pub fn split_first(&self) -> Option<(&T, Cursor<'_, T, B, C>)> {
+        let mut rest = self.cursor_front();
+        let first = rest.get()?;
+        rest.move_next();
+        Some((first, rest))
+    }
+
+    /// Returns the last element and a cursor at the last element of the rest
+    /// of the sequence, to be moved backwards with
+    /// [`move_prev`](Cursor::move_prev). If there is no rest, the cursor is
+    /// past the end. Returns `None` if the sequence is empty.
+    #[must_use]
+    pub fn split_last(&self) -> Option<(&T, Cursor<'_, T, B, C>)> {
+        let mut rest = self.cursor_back();
+        let last = rest.get()?;
+        if rest.index() == 0 {
+            rest.move_next();
+        } else {
+            rest.move_prev();
+        }
+        Some((last, rest))
+    }
+
     #[inline]
     pub fn push_front(&mut self, value: T) {
         self.insert(0, value);
@@ -107,481 +334,4329 @@ impl<T> BVec<T> {
         CursorInner::new_past_the_end(self).insert(value);
     }
 
-    #[inline]
-    pub fn clear(&mut self) {
-        self.drain(..);
+    pub fn pop_front(&mut self) -> Option<T> {
+        CursorInner::<ownership::Mut, T, B, C>::try_new_inbounds_first(self).map(|mut c| c.remove())
     }
 
-    /// # Panics
-    /// Panics if `index > self.len()`.
-    pub fn insert(&mut self, index: usize, value: T) {
-        self.cursor_at_mut(index).insert(value);
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.is_not_empty().then(|| unsafe {
+            CursorInner::<ownership::Mut, T, B, C>::new_last_unchecked(self).remove()
+        })
     }
 
-    /// # Panics
-    /// Panics if `index >= self.len()`.
-    pub fn remove(&mut self, index: usize) -> T {
-        self.cursor_at_mut(index).remove()
+    pub fn clear(&mut self) {
+        if let Some(root) = self.root() {
+            self.len = 0;
+            unsafe { free_tree(root) };
+        }
     }
 
+    /// Takes all elements out of `self`, leaving it empty, like [`mem::take`].
+    ///
+    /// No elements are moved or cloned: the returned tree takes over the
+    /// nodes of `self`.
     #[must_use]
-    pub fn iter(&self) -> Iter<T> {
-        unsafe { Iter::new(self, 0, self.len()) }
+    pub fn take(&mut self) -> Self {
+        mem::take(self)
     }
 
-    pub fn drain<R>(&mut self, range: R) -> Drain<T>
-    where
-        R: RangeBounds<usize>,
-    {
-        Drain::new(self, range)
+    /// Rebuilds the tree so that it uses as few nodes as possible.
+    ///
+    /// Removals can leave many nodes only half full. This moves all elements
+    /// into completely filled leaves in a single O(n) pass, which saves
+    /// memory and makes later traversals faster.
+    pub fn shrink_to_fit(&mut self) {
+        let Some(root) = self.root() else {
+            return;
+        };
+        self.len = 0;
+        let mut builder = BVecBuilder::new_with_params();
+        unsafe { free_tree_with(root, |mut values| builder.extend(values.drain())) };
+        *self = builder.finish();
     }
 
+    /// Returns the number of levels in the tree, which is zero for an empty
+    /// sequence and one if all elements fit into a single leaf.
     #[must_use]
-    pub fn cursor_at(&self, index: usize) -> Cursor<T> {
-        Cursor::new(self, index)
+    pub fn height(&self) -> usize {
+        self.root()
+            .map_or(0, |root| unsafe { usize::from(root.as_ref().height()) + 1 })
     }
 
+    /// Returns the number of nodes in the tree. This walks all internal
+    /// nodes, so it takes O(n / `LEAF_CAP`) time.
     #[must_use]
-    pub fn cursor_at_mut(&mut self, index: usize) -> CursorMut<T> {
-        CursorMut::new(self, index)
+    pub fn node_count(&self) -> usize {
+        self.count_nodes().0
     }
-}
 
-impl<T> Drop for BVec<T> {
-    fn drop(&mut self) {
-        self.clear();
+    /// Returns the total number of elements that fit into the currently
+    /// allocated leaves. Like [`node_count`](BVec::node_count), this walks
+    /// the tree.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.count_nodes().1 * NodeBase::<T, B, C>::LEAF_CAP
     }
-}
 
-impl<T> Default for BVec<T> {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+    /// Returns the number of all nodes and of leaves in the tree.
+    fn count_nodes(&self) -> (usize, usize) {
+        unsafe fn count<T, const B: usize, const C: usize>(
+            node: NodePtr<T, B, C>,
+        ) -> (usize, usize) {
+            unsafe {
+                if node.as_ref().height() == 0 {
+                    return (1, 1);
+                }
+                let mut handle = InternalRef::new(node);
+                (0..handle.len_children())
+                    .map(|i| count((*handle.internal_ptr()).children[i].assume_init()))
+                    .fold((1, 0), |(nodes, leaves), (n, l)| (nodes + n, leaves + l))
+            }
+        }
 
-impl<T: fmt::Debug> fmt::Debug for BVec<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
+        self.root().map_or((0, 0), |root| unsafe { count(root) })
     }
-}
 
-impl<T: Hash> Hash for BVec<T> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.len().hash(state);
-        self.iter().for_each(|elem| elem.hash(state));
+    /// Binary searches this sorted sequence for `x`. Returns the index of a
+    /// matching element, or the index where `x` could be inserted to keep the
+    /// sequence sorted.
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|p| p.cmp(x))
     }
-}
 
-impl<T> Extend<T> for BVec<T> {
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        let mut cursor = CursorInner::new_past_the_end(self);
-        for v in iter {
-            cursor.insert(v);
-            cursor.leaf_index += 1;
+    /// Binary searches this sequence with a comparator function, which must
+    /// be consistent with the order of the elements.
+    ///
+    /// See [`binary_search`](BVec::binary_search).
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let index = self.partition_point(|x| f(x) == Ordering::Less);
+        match self.get(index) {
+            Some(x) if f(x) == Ordering::Equal => Ok(index),
+            _ => Err(index),
         }
     }
-}
 
-impl<T> Index<usize> for BVec<T> {
-    type Output = T;
-    fn index(&self, index: usize) -> &Self::Output {
-        self.get(index)
-            .unwrap_or_else(|| panic_out_of_bounds(index, self.len()))
+    /// Binary searches this sequence, which must be sorted by the key
+    /// extracted by `f`.
+    ///
+    /// See [`binary_search`](BVec::binary_search).
+    pub fn binary_search_by_key<K, F>(&self, b: &K, mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.binary_search_by(|k| f(k).cmp(b))
     }
-}
 
-impl<T> IndexMut<usize> for BVec<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        let len = self.len();
-        self.get_mut(index)
-            .unwrap_or_else(|| panic_out_of_bounds(index, len))
+    /// Returns the index of the first element for which `pred` returns
+    /// `false`, or the length if there is none. The sequence must be
+    /// partitioned so that `pred` is `true` for a prefix of it and `false` for
+    /// the rest, like for [`slice::partition_point`].
+    ///
+    /// This takes O(log² n) time, since every probe descends from a node to
+    /// the first element below it.
+    pub fn partition_point<P>(&self, pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.root()
+            .map_or(0, |root| unsafe { partition_point(root, pred) })
     }
-}
-
-#[cfg(test)]
-mod tests {
-    extern crate std;
-
-    use core::hash::Hasher;
 
-    use super::*;
+    /// Returns a cursor at the first element that is not less than `x`, or
+    /// past the end if there is none. This sequence must be sorted.
+    ///
+    /// Inserting `x` at the cursor keeps the sequence sorted, and puts it
+    /// before any elements equal to it.
+    pub fn lower_bound_mut(&mut self, x: &T) -> CursorMut<'_, T, B, C>
+    where
+        T: Ord,
+    {
+        CursorMut::new_partition_point(self, |y| y < x)
+    }
 
-    fn _assert_cursor_mut_lifetime_covariant<'a, 'b>(x: CursorMut<'a, i32>) -> CursorMut<'b, i32>
+    /// Returns a cursor at the first element that is greater than `x`, or
+    /// past the end if there is none. This sequence must be sorted.
+    ///
+    /// Inserting `x` at the cursor keeps the sequence sorted, and puts it
+    /// after any elements equal to it.
+    pub fn upper_bound_mut(&mut self, x: &T) -> CursorMut<'_, T, B, C>
     where
-        'a: 'b,
+        T: Ord,
     {
-        x
+        CursorMut::new_partition_point(self, |y| y <= x)
     }
 
-    fn _assert_cursor_lifetime_covariant<'a, 'b>(x: Cursor<'a, i32>) -> Cursor<'b, i32>
+    /// Inserts `value` into this sorted sequence after all elements that are
+    /// less than or equal to it, and returns its index.
+    ///
+    /// The position is found with the same descent that the insertion uses,
+    /// see [`upper_bound_mut`](BVec::upper_bound_mut).
+    ///
+    /// # Panics
+    /// Panics if the length of the sequence would overflow.
+    pub fn insert_sorted(&mut self, value: T) -> usize
     where
-        'a: 'b,
+        T: Ord,
     {
-        x
+        self.insert_sorted_by(value, T::cmp)
     }
 
-    fn _assert_cursor_value_covariant<'a, 'b, 'c>(x: Cursor<'a, &'b str>) -> Cursor<'a, &'c str>
+    /// Like [`insert_sorted`](BVec::insert_sorted), but for a sequence sorted
+    /// by the comparator `compare`. `value` is inserted after all elements
+    /// that don't compare greater than it.
+    ///
+    /// # Panics
+    /// Panics if the length of the sequence would overflow.
+    pub fn insert_sorted_by<F>(&mut self, value: T, mut compare: F) -> usize
     where
-        'b: 'c,
+        F: FnMut(&T, &T) -> Ordering,
     {
-        x
+        let mut cursor =
+            CursorMut::new_partition_point(self, |x| compare(x, &value) != Ordering::Greater);
+        cursor.insert(value);
+        cursor.index()
     }
 
-    #[test]
-    fn test_new() {
-        const _: BVec<i32> = BVec::new();
-        let _ = BVec::<usize>::new();
+    /// Like [`insert_sorted`](BVec::insert_sorted), but for a sequence sorted
+    /// by the key extracted by `f`. `value` is inserted after all elements
+    /// with a key less than or equal to its key.
+    ///
+    /// # Panics
+    /// Panics if the length of the sequence would overflow.
+    pub fn insert_sorted_by_key<K, F>(&mut self, value: T, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        let key = f(&value);
+        self.insert_sorted_by(value, |x, _| f(x).cmp(&key))
     }
 
-    #[test]
-    fn test_new_zst() {
-        const _: BVec<()> = BVec::new();
-        let _ = BVec::<()>::new();
+    /// Inserts `value` into this sorted sequence unless an equal element is
+    /// already present, and returns whether it was inserted.
+    ///
+    /// Both the lookup and the insertion use the cursor from a single
+    /// descent, see [`lower_bound_mut`](BVec::lower_bound_mut). Using only
+    /// this to insert keeps the sequence sorted and free of duplicates.
+    ///
+    /// # Panics
+    /// Panics if the length of the sequence would overflow.
+    pub fn insert_if_absent(&mut self, value: T) -> bool
+    where
+        T: Ord,
+    {
+        let mut cursor = self.lower_bound_mut(&value);
+        if cursor.get() == Some(&value) {
+            return false;
+        }
+        cursor.insert(value);
+        true
     }
 
-    #[test]
-    fn test_bvec_size() {
-        use core::mem::size_of;
+    /// Swaps the elements at indices `a` and `b`.
+    ///
+    /// # Panics
+    /// Panics if `a` or `b` is out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        let len = self.len();
+        let pa: *mut T = self
+            .get_mut(a)
+            .unwrap_or_else(|| panic_out_of_bounds(a, len));
+        if a == b {
+            return;
+        }
+        let pb: *mut T = self
+            .get_mut(b)
+            .unwrap_or_else(|| panic_out_of_bounds(b, len));
+        // SAFETY: `a != b`, so the pointers point to distinct elements of the tree.
+        unsafe { ptr::swap_nonoverlapping(pa, pb, 1) };
+    }
 
-        assert_eq!(
-            size_of::<BVec<i32>>(),
-            size_of::<*mut ()>() + size_of::<usize>()
-        )
+    /// Rotates the sequence in place so that the element at `mid` becomes the first one.
+    ///
+    /// # Panics
+    /// Panics if `mid > self.len()`.
+    pub fn rotate_left(&mut self, mid: usize) {
+        assert!(mid <= self.len());
+        let mut tail = self.split_off(mid);
+        tail.append(self);
+        *self = tail;
     }
 
-    #[test]
-    fn test_push_front_back() {
-        let mut b = BVec::<i32>::new();
-        let mut l = 0;
-        for x in 0..500 {
-            b.push_back(x);
-            l += 1;
-            assert_eq!(l, b.len());
+    /// Overwrites the elements starting at `index` with the items of
+    /// `values`, and returns how many were written.
+    ///
+    /// This stops when either `values` or the sequence runs out, so the
+    /// length never changes. Items that don't fit are not consumed from the
+    /// iterator.
+    ///
+    /// # Panics
+    /// Panics if `index > self.len()`.
+    pub fn write_from<I>(&mut self, index: usize, values: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut written = 0;
+        for (slot, value) in self.range_mut(index..).zip(values) {
+            *slot = value;
+            written += 1;
         }
+        written
+    }
 
-        for x in (-500..0).rev() {
-            b.push_front(x);
-            l += 1;
-            assert_eq!(l, b.len());
+    /// Copies the elements in `src` to the range starting at `dest`, like
+    /// [`slice::copy_within`]. The ranges may overlap.
+    ///
+    /// Two cursors walk over the ranges, towards the front if `dest` is
+    /// after the start of `src` and towards the back otherwise, so that no
+    /// element is overwritten before it is copied.
+    ///
+    /// # Panics
+    /// Panics if `src` is out of bounds, its start is after its end or
+    /// `dest + src.len()` is greater than `self.len()`.
+    pub fn copy_within<R>(&mut self, src: R, dest: usize)
+    where
+        R: RangeBounds<usize>,
+        T: Copy,
+    {
+        let (start, end) = resolve_range(&src, self.len());
+        let count = end - start;
+        assert!(dest <= self.len() - count, "dest is out of bounds");
+        if count == 0 || dest == start {
+            return;
         }
 
-        for (a, b) in b.iter().zip(-500..) {
-            assert_eq!(*a, b);
+        let backwards = dest > start;
+        let (first_src, first_dest) = if backwards {
+            (end - 1, dest + count - 1)
+        } else {
+            (start, dest)
+        };
+        let mut src = unsafe {
+            CursorInner::<ownership::Mut, T, B, C>::try_new_inbounds(self, first_src)
+                .unwrap_unchecked()
+        };
+        // SAFETY: the cursors only copy elements, which doesn't change the
+        // tree, and the references they return are never alive at once.
+        unsafe {
+            let mut dest = src.duplicate();
+            dest.move_inbounds_unchecked(first_dest.wrapping_sub(first_src) as isize);
+            for i in 0..count {
+                let value = *src.get_unchecked_mut();
+                *dest.get_unchecked_mut() = value;
+                if i + 1 == count {
+                    break;
+                }
+                if backwards {
+                    src.move_prev_inbounds_unchecked();
+                    dest.move_prev_inbounds_unchecked();
+                } else {
+                    src.move_next_inbounds_unchecked();
+                    dest.move_next_inbounds_unchecked();
+                }
+            }
         }
     }
 
-    #[test]
-    fn test_push_pop_front_back() {
-        use alloc::vec::Vec;
-        use rand::{Rng, SeedableRng};
+    /// Rotates the elements in `range` in place so that the element at
+    /// `range.start + mid` comes first, like `slice[range].rotate_left(mid)`.
+    ///
+    /// This reverses both parts of the range and then the whole range.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds, its start is after its end or
+    /// `mid` is greater than the length of the range.
+    pub fn rotate_range<R>(&mut self, range: R, mid: usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(&range, self.len());
+        assert!(
+            mid <= end - start,
+            "mid {mid} out of range for a range of length {}",
+            end - start
+        );
+        self.reverse_range(start..start + mid);
+        self.reverse_range(start + mid..end);
+        self.reverse_range(start..end);
+    }
 
-        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+    /// Rotates the sequence in place so that the last `k` elements come first.
+    ///
+    /// # Panics
+    /// Panics if `k > self.len()`.
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.len());
+        self.rotate_left(self.len() - k);
+    }
 
-        let mut b = BVec::<i32>::new();
-        let mut v = Vec::new();
-        for x in 0..1000 {
-            if rng.gen() {
-                b.push_back(x);
-                v.push(x);
-            } else {
-                b.push_front(x);
-                v.insert(0, x);
-            }
+    /// Reverses the order of the elements in place.
+    pub fn reverse(&mut self) {
+        if let Some(root) = self.root() {
+            unsafe { reverse(root) };
         }
+    }
 
-        for _ in 0..1000 {
-            let (x, y) = if rng.gen() {
-                (b.remove(b.len() - 1), v.pop().unwrap())
-            } else {
-                (b.remove(0), v.remove(0))
-            };
-            assert_eq!(x, y);
-            assert_eq!(b.len(), v.len());
+    /// Reverses the order of the elements in `range` in place.
+    ///
+    /// Two cursors walk towards each other from both ends of the range,
+    /// swapping the elements they point at.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds or its start is after its end.
+    pub fn reverse_range<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(&range, self.len());
+        if end - start < 2 {
+            return;
         }
-    }
 
-    #[test]
-    fn test_random_insertions() {
-        use alloc::vec::Vec;
-        use rand::{Rng, SeedableRng};
+        let mut front = unsafe {
+            CursorInner::<ownership::Mut, T, B, C>::try_new_inbounds(self, start).unwrap_unchecked()
+        };
+        // SAFETY: the cursors never point at the same element and only swap
+        // elements, which doesn't change the tree.
+        unsafe {
+            let mut back = front.duplicate();
+            back.move_inbounds_unchecked((end - 1 - start) as isize);
+            for _ in 0..(end - start) / 2 {
+                ptr::swap(front.get_unchecked_mut(), back.get_unchecked_mut());
+                front.move_next_inbounds_unchecked();
+                back.move_prev_inbounds_unchecked();
+            }
+        }
+    }
 
-        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+    /// Keeps only the elements for which `f` returns `true`, preserving their order.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|value| f(value));
+    }
 
-        let mut v = Vec::new();
-        let mut b_4_5 = BVec::<i32>::new();
-        let mut b_5_4 = BVec::<i32>::new();
+    /// Like [`retain`](BVec::retain), but returns the number of removed
+    /// elements.
+    pub fn retain_count<F>(&mut self, f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let old_len = self.len();
+        self.retain(f);
+        old_len - self.len()
+    }
 
-        for x in 0..1000 {
-            let index = rng.gen_range(0..=v.len());
-            v.insert(index, x);
-            b_4_5.insert(index, x);
-            b_5_4.insert(index, x);
-            assert_eq!(v.len(), b_4_5.len());
-            assert_eq!(v.len(), b_5_4.len());
+    /// Like [`retain`](BVec::retain), but `f` can also modify the elements.
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_at_mut(0);
+        while let Some(value) = cursor.get_mut() {
+            if f(value) {
+                cursor.move_next();
+            } else {
+                cursor.remove();
+            }
         }
+    }
 
-        assert_eq!(v, b_4_5.iter().copied().collect::<Vec<_>>());
-        assert_eq!(v, b_5_4.iter().copied().collect::<Vec<_>>());
+    /// Returns an iterator that removes and yields the elements for which
+    /// `pred` returns `true`, in order. The other elements are kept.
+    ///
+    /// If the iterator is dropped early, the rest of the elements are still
+    /// checked and the matching ones are removed and dropped.
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F, B, C>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf::new(self, pred)
+    }
+
+    /// Replaces every element with the result of calling `f` on it, without
+    /// requiring `T: Clone`.
+    ///
+    /// If `f` panics, the element it was given is removed from the sequence,
+    /// since `f` has taken ownership of it.
+    pub fn map_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T) -> T,
+    {
+        /// Removes the element at the cursor without dropping it.
+        struct RemoveGuard<'r, 'a, T, const B: usize, const C: usize>(
+            &'r mut CursorMut<'a, T, B, C>,
+        );
+
+        impl<'r, 'a, T, const B: usize, const C: usize> Drop for RemoveGuard<'r, 'a, T, B, C> {
+            fn drop(&mut self) {
+                mem::forget(self.0.remove());
+            }
+        }
+
+        let mut cursor = self.cursor_at_mut(0);
+        while let Some(value) = cursor.get_mut() {
+            let value: *mut T = value;
+            let guard = RemoveGuard(&mut cursor);
+            unsafe { value.write(f(value.read())) };
+            mem::forget(guard);
+            cursor.move_next();
+        }
+    }
+
+    /// Removes the elements at `len..`. Does nothing if `len >= self.len()`.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            self.drain(len..);
+        }
+    }
+
+    /// Removes the elements at `..self.len() - keep`, keeping only the last
+    /// `keep` elements. Does nothing if `keep >= self.len()`.
+    pub fn truncate_front(&mut self, keep: usize) {
+        if keep < self.len() {
+            self.drain(..self.len() - keep);
+        }
+    }
+
+    /// # Panics
+    /// Panics if `index > self.len()`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.cursor_at_mut(index).insert(value);
+    }
+
+    /// Inserts `value` at `index` like [`insert`](BVec::insert), but gives
+    /// the value back instead of panicking if `index > len` or if the length
+    /// would overflow.
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), T> {
+        if index > self.len() || self.len() == isize::MAX as usize {
+            return Err(value);
+        }
+        self.cursor_at_mut(index).insert(value);
+        Ok(())
+    }
+
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.cursor_at_mut(index).remove()
+    }
+
+    /// Removes the element at `index` and replaces it with the last element.
+    ///
+    /// # Panics
+    /// Panics if `index >= self.len()`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        if index >= len {
+            panic_out_of_bounds(index, len);
+        }
+        self.swap(index, len - 1);
+        unsafe { self.pop_back().unwrap_unchecked() }
+    }
+
+    /// Moves all the elements of `other` to the end of `self`, leaving `other` empty.
+    ///
+    /// The two trees are joined structurally: the root of the shorter tree is
+    /// adopted by the taller one at the matching height, so the cost depends on
+    /// the difference of their heights rather than on the number of elements.
+    ///
+    /// # Panics
+    /// Panics if the combined length overflows.
+    pub fn append(&mut self, other: &mut Self) {
+        let Some(other_root) = other.root() else {
+            return;
+        };
+        let Some(root) = self.root() else {
+            mem::swap(self, other);
+            return;
+        };
+
+        let len = self
+            .len
+            .checked_add(other.len)
+            .filter(|&len| len <= isize::MAX as usize)
+            .unwrap_or_else(|| panic_length_overflow());
+        let other_len = mem::take(&mut other.len);
+
+        let new_root = unsafe {
+            join(
+                RawNodeWithLen(self.len, root),
+                RawNodeWithLen(other_len, other_root),
+            )
+        };
+        self.root.write(new_root);
+        self.len = len;
+    }
+
+    /// Appends all elements of `iter` to the end of the sequence.
+    ///
+    /// Unlike [`extend`](Extend::extend), which inserts the elements one by
+    /// one, this collects them into full leaves with a [`BVecBuilder`] and
+    /// joins the result onto the tree at once like [`append`](BVec::append),
+    /// which takes `O(k + log n)` time.
+    ///
+    /// # Panics
+    /// Panics if the new length would overflow.
+    pub fn append_from_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut tail: Self = iter.into_iter().collect();
+        self.append(&mut tail);
+    }
+
+    /// Clones and appends all elements of `other` to the end of the sequence,
+    /// see [`append_from_iter`](BVec::append_from_iter).
+    ///
+    /// # Panics
+    /// Panics if the new length would overflow.
+    pub fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        self.append_from_iter(other.iter().cloned());
+    }
+
+    /// Inserts clones of `values` at `index`, shifting the elements after it.
+    ///
+    /// The tree is split at `index` and the new elements are joined in
+    /// between as a whole subtree, which takes `O(k + log n)` time instead
+    /// of inserting them one by one.
+    ///
+    /// # Panics
+    /// Panics if `index > len` or if the new length would overflow.
+    pub fn insert_slice(&mut self, index: usize, values: &[T])
+    where
+        T: Clone,
+    {
+        let mut tail = self.split_off(index);
+        self.extend_from_slice(values);
+        self.append(&mut tail);
+    }
+
+    /// Splits the sequence in two at `at`. Returns the elements at `at..`,
+    /// leaving the elements at `..at` in `self`.
+    ///
+    /// Like [`append`](BVec::append), this restructures the tree along the
+    /// path to `at` instead of moving elements.
+    ///
+    /// # Panics
+    /// Panics if `at > self.len()`.
+    #[must_use]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        if at > len {
+            panic_out_of_bounds(at, len);
+        }
+        let Some(root) = self.root().filter(|_| at < len) else {
+            return Self::new_with_params();
+        };
+        if at == 0 {
+            return mem::take(self);
+        }
+
+        let (left, right) = unsafe { split(RawNodeWithLen(len, root), at) };
+        self.root.write(left.1);
+        self.len = left.0;
+        Self {
+            root: MaybeUninit::new(right.1),
+            len: right.0,
+        }
+    }
+
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    #[must_use]
+    pub fn into_vec(mut self) -> Vec<T> {
+        self.take_vec()
+    }
+
+    /// Moves all elements into a `Vec` leaf by leaf and frees the nodes,
+    /// leaving the sequence empty.
+    fn take_vec(&mut self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.len());
+        if let Some(root) = self.root() {
+            self.len = 0;
+            unsafe { free_tree_with(root, |mut values| v.extend(values.drain())) };
+        }
+        v
+    }
+
+    /// Sorts the sequence, preserving the order of equal elements.
+    ///
+    /// The elements are moved into a `Vec`, sorted there and collected back
+    /// into full leaves, which takes O(n log n) time in total.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Like [`sort`](BVec::sort), but with a comparator function.
+    ///
+    /// If `compare` panics, the elements are left in an unspecified order.
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.sort_in_vec(|v| v.sort_by(compare));
+    }
+
+    /// Sorts the sequence without preserving the order of equal elements,
+    /// see [`sort`](BVec::sort).
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_unstable_by(T::cmp);
+    }
+
+    /// Like [`sort_unstable`](BVec::sort_unstable), but with a comparator
+    /// function.
+    ///
+    /// If `compare` panics, the elements are left in an unspecified order.
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.sort_in_vec(|v| v.sort_unstable_by(compare));
+    }
+
+    /// Moves the elements into a `Vec`, calls `sort` on it and rebuilds the
+    /// tree from the result, even if `sort` panics.
+    fn sort_in_vec(&mut self, sort: impl FnOnce(&mut Vec<T>)) {
+        struct RebuildGuard<'r, T, const B: usize, const C: usize> {
+            tree: &'r mut BVec<T, B, C>,
+            values: Vec<T>,
+        }
+
+        impl<'r, T, const B: usize, const C: usize> Drop for RebuildGuard<'r, T, B, C> {
+            fn drop(&mut self) {
+                *self.tree = mem::take(&mut self.values).into_iter().collect();
+            }
+        }
+
+        let values = self.take_vec();
+        let mut guard = RebuildGuard { tree: self, values };
+        sort(&mut guard.values);
+    }
+
+    /// Collects references to all elements into a `Vec`, for passing them
+    /// to code that needs a slice.
+    #[must_use]
+    pub fn as_contiguous(&self) -> Vec<&T> {
+        let mut v = Vec::with_capacity(self.len());
+        v.extend(self);
+        v
+    }
+
+    /// Moves all elements into a `Vec`, freeing the nodes along the way.
+    /// This is the same as [`into_vec`](BVec::into_vec).
+    #[must_use]
+    pub fn into_contiguous(self) -> Vec<T> {
+        self.into_vec()
+    }
+
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, T, B, C> {
+        unsafe { Iter::new(self, 0, self.len()) }
+    }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, B, C> {
+        let len = self.len();
+        unsafe { IterMut::new(self, 0, len) }
+    }
+
+    /// Returns an iterator over the elements in `range`.
+    ///
+    /// # Panics
+    /// Panics if the start of the range is after its end or if the end is
+    /// out of bounds.
+    #[must_use]
+    #[track_caller]
+    pub fn range<R>(&self, range: R) -> Iter<'_, T, B, C>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(&range, self.len());
+        unsafe { Iter::new(self, start, end) }
+    }
+
+    /// Returns an iterator over mutable references to the elements in `range`.
+    ///
+    /// # Panics
+    /// Panics if the start of the range is after its end or if the end is
+    /// out of bounds.
+    #[must_use]
+    #[track_caller]
+    pub fn range_mut<R>(&mut self, range: R) -> IterMut<'_, T, B, C>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = resolve_range(&range, self.len());
+        unsafe { IterMut::new(self, start, end) }
+    }
+
+    /// Returns a new `BVec` with clones of the elements in `range`.
+    ///
+    /// The clones are collected into full leaves with a [`BVecBuilder`], so
+    /// this takes `O(k + log n)` time for `k` elements.
+    ///
+    /// # Panics
+    /// Panics if the start of the range is after its end or if the end is
+    /// out of bounds.
+    #[must_use]
+    #[track_caller]
+    pub fn subrange<R>(&self, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+        T: Clone,
+    {
+        self.range(range).cloned().collect()
+    }
+
+    /// Returns an iterator over chunks of `chunk_size` elements, starting
+    /// from the front. The last chunk is shorter if the length is not
+    /// divisible by `chunk_size`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[must_use]
+    pub fn chunks(&self, chunk_size: usize) -> Chunks<'_, T, B, C> {
+        Chunks::new(self, chunk_size)
+    }
+
+    /// Like [`chunks`](BVec::chunks), but yields mutable references.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[must_use]
+    pub fn chunks_mut(&mut self, chunk_size: usize) -> ChunksMut<'_, T, B, C> {
+        ChunksMut::new(self, chunk_size)
+    }
+
+    /// Like [`chunks`](BVec::chunks), but starts from the back.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[must_use]
+    pub fn rchunks(&self, chunk_size: usize) -> RChunks<'_, T, B, C> {
+        RChunks::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over all runs of `size` consecutive elements.
+    /// There are none if the sequence is shorter than `size`.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn windows(&self, size: usize) -> Windows<'_, T, B, C> {
+        Windows::new(self, size)
+    }
+
+    /// Returns iterators over mutable references to the elements at `..mid`
+    /// and `mid..`, which can be used at the same time.
+    ///
+    /// # Panics
+    /// Panics if `mid > len`.
+    #[must_use]
+    pub fn split_at_mut(&mut self, mid: usize) -> (IterMut<'_, T, B, C>, IterMut<'_, T, B, C>) {
+        let len = self.len();
+        if mid > len {
+            panic_out_of_bounds(mid, len);
+        }
+        self.iter_mut().split_at(mid)
+    }
+
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, B, C>
+    where
+        R: RangeBounds<usize>,
+    {
+        Drain::new(self, range)
+    }
+
+    /// Replaces the elements in `range` with the ones from `replace_with`.
+    ///
+    /// The returned iterator yields the removed elements. Like with
+    /// `Vec::splice`, the range is removed and the new elements are inserted
+    /// when the iterator is dropped, even if it was not fully consumed.
+    ///
+    /// # Panics
+    /// Panics if the start of the range is after its end or if the end is
+    /// out of bounds.
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, B, C>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        Splice::new(self, range, replace_with.into_iter())
+    }
+
+    #[must_use]
+    pub fn cursor_at(&self, index: usize) -> Cursor<'_, T, B, C> {
+        Cursor::new(self, index)
+    }
+
+    #[must_use]
+    pub fn cursor_at_mut(&mut self, index: usize) -> CursorMut<'_, T, B, C> {
+        CursorMut::new(self, index)
+    }
+
+    /// Returns a cursor at `index`, or `None` if `index > self.len()`.
+    ///
+    /// Together with [`Cursor::index`], this allows storing the position of
+    /// a cursor as a plain `usize`. A stored index doesn't follow the
+    /// elements, so it points elsewhere after insertions or removals before
+    /// it.
+    #[must_use]
+    pub fn cursor_at_checked(&self, index: usize) -> Option<Cursor<'_, T, B, C>> {
+        (index <= self.len()).then(|| Cursor::new(self, index))
+    }
+
+    /// Returns two independent cursors at `i` and `j`. Since they only read
+    /// the tree, both can be moved and used at the same time, e.g. to merge
+    /// two parts of the sequence.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is greater than the length.
+    #[must_use]
+    pub fn cursor_pair(&self, i: usize, j: usize) -> (Cursor<'_, T, B, C>, Cursor<'_, T, B, C>) {
+        (self.cursor_at(i), self.cursor_at(j))
+    }
+
+    /// Returns a cursor pointing at the first element, or past the end if
+    /// the tree is empty.
+    #[must_use]
+    pub fn cursor_front(&self) -> Cursor<'_, T, B, C> {
+        Cursor::new_front(self)
+    }
+
+    /// Returns a mutable cursor pointing at the first element, or past the
+    /// end if the tree is empty.
+    #[must_use]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, B, C> {
+        CursorMut::new_front(self)
+    }
+
+    /// Returns a cursor pointing at the last element, or past the end if
+    /// the tree is empty.
+    #[must_use]
+    pub fn cursor_back(&self) -> Cursor<'_, T, B, C> {
+        Cursor::new_back(self)
+    }
+
+    /// Returns a mutable cursor pointing at the last element, or past the
+    /// end if the tree is empty.
+    #[must_use]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, B, C> {
+        CursorMut::new_back(self)
+    }
+
+    /// Returns the index of the first element that satisfies `pred`.
+    pub fn position<P>(&self, pred: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().position(pred)
+    }
+
+    /// Returns the index of the last element that satisfies `pred`.
+    pub fn rposition<P>(&self, mut pred: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut cursor = self.cursor_back();
+        while let Some(value) = cursor.get() {
+            if pred(value) {
+                return Some(cursor.index());
+            }
+            if cursor.index() == 0 {
+                break;
+            }
+            cursor.move_prev();
+        }
+        None
+    }
+
+    /// Returns a cursor pointing at the first element that satisfies `pred`,
+    /// or `None` if there is no such element.
+    pub fn find_cursor<P>(&self, pred: P) -> Option<Cursor<'_, T, B, C>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Cursor::find(self, pred)
+    }
+
+    /// Returns a mutable cursor pointing at the first element that satisfies
+    /// `pred`, or `None` if there is no such element.
+    pub fn find_cursor_mut<P>(&mut self, pred: P) -> Option<CursorMut<'_, T, B, C>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        CursorMut::find(self, pred)
+    }
+
+    /// Returns the number of elements equal to `x`.
+    #[must_use]
+    pub fn count(&self, x: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        LeafSlices::new(self)
+            .map(|values| values.iter().filter(|&v| v == x).count())
+            .sum()
+    }
+
+    /// Returns the smallest element, or `None` if the sequence is empty. If
+    /// several elements are equally small, the first one is returned.
+    ///
+    /// This isn't called `min` because that name is taken by [`Ord::min`].
+    /// Like the other methods built on [`Iterator::fold`], it scans the
+    /// leaves as slices.
+    #[must_use]
+    pub fn min_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().min()
+    }
+
+    /// Returns the largest element, or `None` if the sequence is empty. If
+    /// several elements are equally large, the last one is returned, like
+    /// [`Iterator::max`].
+    #[must_use]
+    pub fn max_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().max()
+    }
+
+    /// Returns the element with the smallest key, or `None` if the sequence
+    /// is empty. If several keys are equally small, the first element is
+    /// returned.
+    pub fn min_by_key<K, F>(&self, mut f: F) -> Option<&T>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.iter().min_by_key(|x| f(x))
+    }
+
+    /// Returns the element with the largest key, or `None` if the sequence
+    /// is empty. If several keys are equally large, the last element is
+    /// returned, like [`Iterator::max_by_key`].
+    pub fn max_by_key<K, F>(&self, mut f: F) -> Option<&T>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.iter().max_by_key(|x| f(x))
+    }
+
+    /// Sums the elements, like `self.iter().sum()`.
+    ///
+    /// The standard numeric types implement [`Sum`] with a fold, which
+    /// scans the leaves as slices.
+    #[must_use]
+    pub fn sum<'a, S>(&'a self) -> S
+    where
+        S: Sum<&'a T>,
+    {
+        self.iter().sum()
+    }
+
+    /// Multiplies the elements, like `self.iter().product()`.
+    #[must_use]
+    pub fn product<'a, P>(&'a self) -> P
+    where
+        P: Product<&'a T>,
+    {
+        self.iter().product()
+    }
+
+    /// Returns `true` if `prefix` is a prefix of the tree.
+    #[must_use]
+    pub fn starts_with(&self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        prefix.len() <= self.len() && self.range(..prefix.len()).eq(prefix)
+    }
+
+    /// Returns `true` if `suffix` is a suffix of the tree.
+    #[must_use]
+    pub fn ends_with(&self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        suffix.len() <= self.len() && self.range(self.len() - suffix.len()..).eq(suffix)
+    }
+}
+
+impl<const B: usize, const C: usize> BVec<u8, B, C> {
+    /// Returns the index of the first byte equal to `needle`.
+    ///
+    /// The bytes are scanned a leaf at a time as slices.
+    #[must_use]
+    pub fn find_byte(&self, needle: u8) -> Option<usize> {
+        let mut offset = 0;
+        for bytes in LeafSlices::new(self) {
+            if let Some(index) = bytes.iter().position(|&b| b == needle) {
+                return Some(offset + index);
+            }
+            offset += bytes.len();
+        }
+        None
+    }
+}
+
+impl<T, const B: usize, const C: usize, const B2: usize, const C2: usize>
+    BVec<BVec<T, B, C>, B2, C2>
+{
+    /// Flattens the inner trees into one tree, keeping their order.
+    ///
+    /// The inner trees are joined with [`append`](BVec::append), so their
+    /// elements are never moved one by one.
+    ///
+    /// # Panics
+    /// Panics if the combined length overflows.
+    #[must_use]
+    pub fn concat(self) -> BVec<T, B, C> {
+        let mut result = BVec::new_with_params();
+        for mut tree in self {
+            result.append(&mut tree);
+        }
+        result
+    }
+
+    /// Flattens the inner trees into one tree, keeping their order and
+    /// placing a clone of `sep` between each pair of adjacent trees.
+    ///
+    /// # Panics
+    /// Panics if the combined length overflows.
+    #[must_use]
+    pub fn join(self, sep: &T) -> BVec<T, B, C>
+    where
+        T: Clone,
+    {
+        let mut result = BVec::new_with_params();
+        for (i, mut tree) in self.into_iter().enumerate() {
+            if i > 0 {
+                result.push_back(sep.clone());
+            }
+            result.append(&mut tree);
+        }
+        result
+    }
+}
+
+// SAFETY: the tree owns its nodes and elements like a `Vec<T>` does, and the
+// node pointers are never shared with another tree.
+unsafe impl<T: Send, const B: usize, const C: usize> Send for BVec<T, B, C> {}
+// SAFETY: a shared reference to the tree only gives out shared references to
+// the elements, and the nodes are not mutated through it.
+unsafe impl<T: Sync, const B: usize, const C: usize> Sync for BVec<T, B, C> {}
+
+impl<T, const B: usize, const C: usize> Drop for BVec<T, B, C> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const B: usize, const C: usize> Default for BVec<T, B, C> {
+    fn default() -> Self {
+        Self::new_with_params()
+    }
+}
+
+impl<T: Clone, const B: usize, const C: usize> Clone for BVec<T, B, C> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+
+    /// Overwrites the elements of `self` in place, so only the nodes needed
+    /// for a difference in length are allocated or freed.
+    fn clone_from(&mut self, source: &Self) {
+        self.truncate(source.len());
+        let mut source_iter = source.iter();
+        for (dst, src) in self.iter_mut().zip(source_iter.by_ref()) {
+            dst.clone_from(src);
+        }
+        self.extend(source_iter.cloned());
+    }
+}
+
+impl<T: fmt::Debug, const B: usize, const C: usize> fmt::Debug for BVec<T, B, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Hash, const B: usize, const C: usize> Hash for BVec<T, B, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hashing whole leaves with `T::hash_slice` would be faster, but
+        // hashers don't have to treat split writes like a single one. Equal
+        // sequences stored in differently shaped trees must hash equally.
+        self.len().hash(state);
+        self.iter().for_each(|elem| elem.hash(state));
+    }
+}
+
+impl<T: PartialEq<U>, U, const B: usize, const C: usize> PartialEq<BVec<U, B, C>>
+    for BVec<T, B, C>
+{
+    fn eq(&self, other: &BVec<U, B, C>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const B: usize, const C: usize> Eq for BVec<T, B, C> {}
+
+impl<T: PartialOrd, const B: usize, const C: usize> PartialOrd for BVec<T, B, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, const B: usize, const C: usize> Ord for BVec<T, B, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T, const B: usize, const C: usize> Extend<T> for BVec<T, B, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut cursor = CursorInner::new_past_the_end(self);
+        for v in iter {
+            cursor.insert(v);
+            cursor.leaf_index += 1;
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, const B: usize, const C: usize> Extend<&'a T> for BVec<T, B, C> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, const B: usize, const C: usize> IntoIterator for BVec<T, B, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T, B, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> IntoIterator for &'a BVec<T, B, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, B, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const B: usize, const C: usize> IntoIterator for &'a mut BVec<T, B, C> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T, B, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const B: usize, const C: usize> FromIterator<T> for BVec<T, B, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut builder = BVecBuilder::new_with_params();
+        builder.extend(iter);
+        builder.finish()
+    }
+}
+
+impl<T, const B: usize, const C: usize> From<VecDeque<T>> for BVec<T, B, C> {
+    fn from(v: VecDeque<T>) -> Self {
+        v.into_iter().collect()
+    }
+}
+
+impl<T, const B: usize, const C: usize> From<BVec<T, B, C>> for VecDeque<T> {
+    fn from(b: BVec<T, B, C>) -> Self {
+        // Converting a `Vec` into a `VecDeque` reuses its buffer.
+        b.into_vec().into()
+    }
+}
+
+impl<T, const B: usize, const C: usize> Index<usize> for BVec<T, B, C> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index)
+            .unwrap_or_else(|| panic_out_of_bounds(index, self.len()))
+    }
+}
+
+impl<T, const B: usize, const C: usize> IndexMut<usize> for BVec<T, B, C> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.len();
+        self.get_mut(index)
+            .unwrap_or_else(|| panic_out_of_bounds(index, len))
+    }
+}
+
+#[cfg(test)]
+impl<T, const B: usize, const C: usize> BVec<T, B, C> {
+    /// Walks the whole tree and panics if any of its structural invariants
+    /// are broken.
+    fn assert_invariants(&self) {
+        use node::handle::LeafRef;
+
+        /// Checks the subtree rooted at `node` and returns its length.
+        unsafe fn check<T, const B: usize, const C: usize>(
+            node: NodePtr<T, B, C>,
+            height: u8,
+            is_root: bool,
+        ) -> usize {
+            unsafe {
+                assert_eq!(node.as_ref().height(), height);
+                if height == 0 {
+                    let leaf = LeafRef::new(node);
+                    assert!(leaf.len() > 0, "empty leaf");
+                    assert!(is_root || !leaf.is_underfull(), "underfull leaf");
+                    return leaf.len();
+                }
+
+                let mut internal = InternalRef::new(node);
+                let len_children = internal.len_children();
+                assert!(len_children >= 2 || !is_root, "root with a single child");
+                assert!(
+                    is_root || !internal.is_underfull(),
+                    "underfull internal node"
+                );
+                let mut total = 0;
+                for i in 0..len_children {
+                    let child = (*internal.internal_ptr()).children[i].assume_init();
+                    assert_eq!(child.as_ref().parent, Some(node));
+                    assert_eq!(usize::from(child.as_ref().parent_index.assume_init()), i);
+                    let child_len = check(child, height - 1, false);
+                    assert_eq!(
+                        internal.sum_lens_below(i + 1) - internal.sum_lens_below(i),
+                        child_len
+                    );
+                    total += child_len;
+                }
+                assert_eq!(internal.len(), total);
+                total
+            }
+        }
+
+        if let Some(root) = self.root() {
+            unsafe {
+                assert!(root.as_ref().parent.is_none());
+                assert_eq!(check(root, root.as_ref().height(), true), self.len);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::hash::Hasher;
+
+    use super::*;
+
+    fn _assert_send_sync() {
+        fn assert_send<T: Send>() {}
+        fn assert_sync<T: Sync>() {}
+        assert_send::<BVec<i32>>();
+        assert_sync::<BVec<i32>>();
+        assert_send::<Iter<'_, i32>>();
+    }
+
+    fn _assert_iters_clone_without_t_clone<T>(b: &BVec<T>) {
+        fn assert_clone<I: Clone>(_: I) {}
+        assert_clone(b.iter());
+        assert_clone(b.chunks(2));
+        assert_clone(b.windows(2));
+    }
+
+    fn _assert_cursor_mut_lifetime_covariant<'a, 'b>(x: CursorMut<'a, i32>) -> CursorMut<'b, i32>
+    where
+        'a: 'b,
+    {
+        x
+    }
+
+    fn _assert_cursor_lifetime_covariant<'a, 'b>(x: Cursor<'a, i32>) -> Cursor<'b, i32>
+    where
+        'a: 'b,
+    {
+        x
+    }
+
+    fn _assert_cursor_value_covariant<'a, 'b, 'c>(x: Cursor<'a, &'b str>) -> Cursor<'a, &'c str>
+    where
+        'b: 'c,
+    {
+        x
+    }
+
+    #[test]
+    fn test_new() {
+        const _: BVec<i32> = BVec::new();
+        let _ = BVec::<usize>::new();
+    }
+
+    #[test]
+    fn test_new_zst() {
+        const _: BVec<()> = BVec::new();
+        let _ = BVec::<()>::new();
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_many_zsts() {
+        let mut b = BVec::<()>::new();
+        for _ in 0..1_000_000 {
+            b.push_back(());
+        }
+        assert_eq!(b.len(), 1_000_000);
+        assert_eq!(b.iter().count(), 1_000_000);
+        assert_eq!(b.iter().fold(0, |n, ()| n + 1), 1_000_000);
+        // Each leaf holds tens of thousands of values.
+        assert!(unsafe { b.root().unwrap().as_ref().height() } <= 1);
+
+        b.insert(500_000, ());
+        assert_eq!(b.get(1_000_000), Some(&()));
+        assert_eq!(b.remove(3), ());
+        assert_eq!(b.len(), 1_000_000);
+        b.truncate(10);
+        assert_eq!(b.len(), 10);
+    }
+
+    #[test]
+    fn test_many_zsts_drop() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        struct Zst;
+        impl Drop for Zst {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let mut b = BVec::<Zst>::new();
+        b.extend((0..200_000).map(|_| Zst));
+        drop(b.remove(100_000));
+        assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+        drop(b.split_off(150_000));
+        assert_eq!(DROPS.load(Ordering::Relaxed), 50_000);
+        drop(b);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 200_000);
+    }
+
+    #[test]
+    fn test_bvec_size() {
+        use core::mem::size_of;
+
+        assert_eq!(
+            size_of::<BVec<i32>>(),
+            size_of::<*mut ()>() + size_of::<usize>()
+        )
+    }
+
+    #[test]
+    fn test_push_front_back() {
+        let mut b = BVec::<i32>::new();
+        let mut l = 0;
+        for x in 0..500 {
+            b.push_back(x);
+            l += 1;
+            assert_eq!(l, b.len());
+        }
+
+        for x in (-500..0).rev() {
+            b.push_front(x);
+            l += 1;
+            assert_eq!(l, b.len());
+        }
+
+        for (a, b) in b.iter().zip(-500..) {
+            assert_eq!(*a, b);
+        }
+    }
+
+    #[test]
+    fn test_push_pop_front_back() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut b = BVec::<i32>::new();
+        let mut v = Vec::new();
+        for x in 0..1000 {
+            if rng.gen() {
+                b.push_back(x);
+                v.push(x);
+            } else {
+                b.push_front(x);
+                v.insert(0, x);
+            }
+        }
+
+        for _ in 0..1000 {
+            let (x, y) = if rng.gen() {
+                (b.remove(b.len() - 1), v.pop().unwrap())
+            } else {
+                (b.remove(0), v.remove(0))
+            };
+            assert_eq!(x, y);
+            assert_eq!(b.len(), v.len());
+        }
+    }
+
+    #[test]
+    fn test_pop_front_back() {
+        use alloc::collections::VecDeque;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut b = BVec::<i32>::new();
+        assert_eq!(b.pop_front(), None);
+        assert_eq!(b.pop_back(), None);
+
+        b.push_back(1);
+        assert_eq!(b.pop_back(), Some(1));
+        assert!(b.is_empty());
+        b.push_back(2);
+        assert_eq!(b.pop_front(), Some(2));
+        assert!(b.is_empty());
+        assert_eq!(b.pop_front(), None);
+
+        let mut v = VecDeque::new();
+        for x in 0..1000 {
+            b.push_back(x);
+            v.push_back(x);
+        }
+        for _ in 0..1100 {
+            if rng.gen() {
+                assert_eq!(b.pop_back(), v.pop_back());
+            } else {
+                assert_eq!(b.pop_front(), v.pop_front());
+            }
+            assert_eq!(b.len(), v.len());
+        }
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_array_vec_split() {
+        use utils::ArrayVecMut;
+
+        for index in 0..=7 {
+            let mut array = [0, 1, 2, 3, 4, 5, 6, 0];
+            let mut len = 7;
+            let mut other_array = [0; 8];
+            let mut other_len = 0;
+            unsafe {
+                let mut head = ArrayVecMut::new(array.as_mut_ptr(), &mut len, 8);
+                let tail = ArrayVecMut::new(other_array.as_mut_ptr(), &mut other_len, 8);
+                head.split(index, tail);
+            }
+            assert_eq!(usize::from(len), index);
+            assert_eq!(usize::from(other_len), 7 - index);
+            assert_eq!(array[..index], [0, 1, 2, 3, 4, 5, 6][..index]);
+            assert_eq!(other_array[..7 - index], [0, 1, 2, 3, 4, 5, 6][index..]);
+        }
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        use alloc::vec::Vec;
+
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..10_000);
+        b.retain(|x| x % 10 == 0);
+        let sparse_capacity = b.capacity();
+
+        b.shrink_to_fit();
+        b.assert_invariants();
+        // Every leaf is full with four values now.
+        assert_eq!(b.capacity(), 1_000);
+        assert!(b.capacity() * 3 < sparse_capacity * 2);
+        assert_eq!(
+            b.iter().copied().collect::<Vec<_>>(),
+            (0..10_000).step_by(10).collect::<Vec<_>>()
+        );
+
+        b.clear();
+        b.shrink_to_fit();
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_height_and_node_count() {
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        assert_eq!((b.height(), b.node_count(), b.capacity()), (0, 0, 0));
+        b.push_back(0);
+        assert_eq!((b.height(), b.node_count(), b.capacity()), (1, 1, 4));
+
+        let mut prev_height = 1;
+        for n in 1..14 {
+            b.extend(b.len() as i32..1 << n);
+            // Every internal node has at least two children and every leaf
+            // at least two values, so the height is logarithmic in the length.
+            assert!(b.height() >= prev_height);
+            assert!(b.height() <= n);
+            prev_height = b.height();
+            assert!(b.node_count() < b.len());
+            assert!(b.capacity() >= b.len());
+        }
+        assert!(b.height() >= 7);
+
+        b.truncate(3);
+        assert_eq!((b.height(), b.node_count(), b.capacity()), (1, 1, 4));
+    }
+
+    #[test]
+    fn test_random_insertions() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut v = Vec::new();
+        let mut b_4_5 = BVec::<i32>::new();
+        let mut b_5_4 = BVec::<i32>::new();
+
+        for x in 0..1000 {
+            let index = rng.gen_range(0..=v.len());
+            v.insert(index, x);
+            b_4_5.insert(index, x);
+            b_5_4.insert(index, x);
+            assert_eq!(v.len(), b_4_5.len());
+            assert_eq!(v.len(), b_5_4.len());
+            b_4_5.assert_invariants();
+            b_5_4.assert_invariants();
+        }
+
+        assert_eq!(v, b_4_5.iter().copied().collect::<Vec<_>>());
+        assert_eq!(v, b_5_4.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_random_removals() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut v = Vec::new();
+        let mut b_4_2 = BVec::<i32>::new();
+        let mut b_5_1 = BVec::<i32>::new();
+
+        for x in 0..1000 {
+            v.push(x);
+            b_4_2.push_back(x);
+            b_5_1.push_back(x);
+        }
+
+        while !v.is_empty() {
+            let index = rng.gen_range(0..v.len());
+            let v_rem = v.remove(index);
+            let b_4_2_rem = b_4_2.remove(index);
+            let b_5_1_rem = b_5_1.remove(index);
+            assert_eq!(v.len(), b_4_2.len());
+            assert_eq!(v.len(), b_5_1.len());
+            assert_eq!(v_rem, b_5_1_rem);
+            assert_eq!(v_rem, b_4_2_rem);
+            b_4_2.assert_invariants();
+            b_5_1.assert_invariants();
+        }
+
+        assert!(b_4_2.is_empty());
+        assert!(b_5_1.is_empty());
+    }
+
+    #[test]
+    fn test_random_double_insertions() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut v = Vec::new();
+        let mut b_7_3 = BVec::<i32, 8, 12>::new_with_params();
+        let mut b_5_5 = BVec::<i32, 8, 20>::new_with_params();
+
+        for x in 0..500 {
+            let index = rng.gen_range(0..=v.len());
+            v.insert(index, 2 * x);
+            v.insert(index, 2 * x + 1);
+            let mut cursor_7_3 = b_7_3.cursor_at_mut(index);
+            let mut cursor_5_5 = b_5_5.cursor_at_mut(index);
+            cursor_7_3.insert(2 * x);
+            cursor_7_3.insert(2 * x + 1);
+            cursor_5_5.insert(2 * x);
+            cursor_5_5.insert(2 * x + 1);
+            assert_eq!(v.len(), b_7_3.len());
+            assert_eq!(v.len(), b_5_5.len());
+            b_7_3.assert_invariants();
+            b_5_5.assert_invariants();
+        }
+
+        assert_eq!(v, b_7_3.iter().copied().collect::<Vec<_>>());
+        assert_eq!(v, b_5_5.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_random_cursor_insert_remove() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut v: Vec<i32> = (0..200).collect();
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..200);
+
+        for x in 0..200 {
+            let index = rng.gen_range(0..=v.len());
+            let mut cursor = b.cursor_at_mut(index);
+            for y in 0..rng.gen_range(1..20) {
+                if rng.gen_bool(0.5) || !cursor.is_inbounds() {
+                    v.insert(index, 1000 * x + y);
+                    cursor.insert(1000 * x + y);
+                    assert_eq!(cursor.get(), Some(&(1000 * x + y)));
+                } else {
+                    assert_eq!(cursor.remove(), v.remove(index));
+                    assert_eq!(cursor.get(), v.get(index));
+                }
+                assert_eq!(cursor.index(), index);
+                assert_eq!(cursor.len(), v.len());
+            }
+            b.assert_invariants();
+        }
+
+        assert_eq!(v, b.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_cursor_move_next_prev() {
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..500);
+
+        let mut c = b.cursor_at(0);
+        for i in 0..500 {
+            assert_eq!(c.get(), Some(&i));
+            c.move_next();
+        }
+        assert_eq!(c.index(), 500);
+        assert_eq!(c.get(), None);
+        for i in (0..500).rev() {
+            c.move_prev();
+            assert_eq!(c.get(), Some(&i));
+        }
+
+        let mut c = b.cursor_at_mut(500);
+        for i in (0..500).rev() {
+            c.move_prev();
+            assert_eq!(c.index(), i as usize);
+            *c.get_mut().unwrap() *= 2;
+        }
+        for i in 0..500 {
+            assert_eq!(c.get(), Some(&(2 * i)));
+            c.move_next();
+        }
+        assert!(!c.is_inbounds());
+    }
+
+    #[test]
+    fn test_cursor_peek() {
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..100);
+
+        let c = b.cursor_at(0);
+        assert_eq!(c.peek_prev(), None);
+        assert_eq!(c.peek_next(), Some(&1));
+        let c = b.cursor_at(99);
+        assert_eq!(c.peek_prev(), Some(&98));
+        assert_eq!(c.peek_next(), None);
+        let c = b.cursor_at(100);
+        assert_eq!(c.peek_prev(), Some(&99));
+        assert_eq!(c.peek_next(), None);
+
+        for i in 1..99 {
+            let c = b.cursor_at_mut(i);
+            assert_eq!(c.peek_prev(), Some(&(i as i32 - 1)));
+            assert_eq!(c.peek_next(), Some(&(i as i32 + 1)));
+            assert_eq!(c.get(), Some(&(i as i32)));
+        }
+
+        let empty = BVec::<i32>::new();
+        assert_eq!(empty.cursor_at(0).peek_prev(), None);
+        assert_eq!(empty.cursor_at(0).peek_next(), None);
+    }
+
+    #[test]
+    fn test_cursor_front_back() {
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        assert_eq!(b.cursor_front().index(), 0);
+        assert_eq!(b.cursor_back().get(), None);
+        assert!(!b.cursor_front_mut().is_inbounds());
+        b.cursor_back_mut().insert(5);
+        assert_eq!(b.first(), Some(&5));
+
+        b.clear();
+        b.extend(0..300);
+        let mut c = b.cursor_front();
+        for i in 0..300 {
+            assert_eq!(c.get(), Some(&i));
+            c.move_next();
+        }
+        let mut c = b.cursor_back();
+        assert_eq!(c.index(), 299);
+        for i in (1..300).rev() {
+            assert_eq!(c.get(), Some(&i));
+            c.move_prev();
+        }
+
+        let mut c = b.cursor_back_mut();
+        c.move_next();
+        c.insert(300);
+        let mut c = b.cursor_front_mut();
+        assert_eq!(c.remove(), 0);
+        assert_eq!(c.get(), Some(&1));
+        assert!(b.iter().copied().eq(1..=300));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cursor_move_next_past_the_end() {
+        let b: BVec<i32> = (0..10).collect();
+        b.cursor_at(10).move_next();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cursor_move_prev_at_start() {
+        let mut b: BVec<i32> = (0..10).collect();
+        b.cursor_at_mut(0).move_prev();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_remove_past_end() {
+        let mut v = BVec::<i32>::new();
+        for x in 0..10 {
+            v.push_back(x);
+        }
+        let mut c = v.cursor_at_mut(10);
+        c.remove();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_remove_past_end_root() {
+        let mut v = BVec::<i32>::new();
+        for x in 0..10 {
+            v.push_back(x);
+        }
+        let mut c = v.cursor_at_mut(10);
+        c.remove();
+    }
+
+    #[test]
+    fn test_random_removals2() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut v = Vec::new();
+        let mut b_4_4 = BVec::<i32, 4, 16>::new_with_params();
+        let mut b_5_5 = BVec::<i32, 8, 20>::new_with_params();
+
+        for x in 0..1000 {
+            v.push(x);
+            b_4_4.push_back(x);
+            b_5_5.push_back(x);
+        }
+
+        while !v.is_empty() {
+            let index = rng.gen_range(0..v.len());
+            let v_rem = v.remove(index);
+            let b_4_4_rem = b_4_4.remove(index);
+            let b_5_5_rem = b_5_5.remove(index);
+            assert_eq!(v.len(), b_4_4.len());
+            assert_eq!(v.len(), b_5_5.len());
+            assert_eq!(v_rem, b_5_5_rem);
+            assert_eq!(v_rem, b_4_4_rem);
+            b_4_4.assert_invariants();
+            b_5_5.assert_invariants();
+        }
+        assert!(b_4_4.is_empty());
+        assert!(b_5_5.is_empty());
+    }
+
+    #[test]
+    fn test_bvec_debug() {
+        use alloc::format;
+        use alloc::vec::Vec;
+
+        let v = Vec::from_iter(0..1000);
+        let mut b = BVec::new();
+        for x in 0..1000 {
+            b.push_back(x);
+        }
+
+        assert_eq!(format!("{v:?}"), format!("{b:?}"));
+    }
+
+    #[test]
+    fn test_random_double_removals() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut v = Vec::new();
+        let mut b_4_4 = BVec::<i32, 4, 16>::new_with_params();
+        let mut b_5_5 = BVec::<i32, 8, 20>::new_with_params();
+
+        for x in 0..1000 {
+            v.push(x);
+            b_4_4.push_back(x);
+            b_5_5.push_back(x);
+        }
+
+        while !v.is_empty() {
+            let index = rng.gen_range(0..v.len() - 1);
+            let v1 = v.remove(index);
+            let v2 = v.remove(index);
+            {
+                let mut cursor_5_5 = b_5_5.cursor_at_mut(index);
+                let b1 = cursor_5_5.remove();
+                let b2 = cursor_5_5.remove();
+                assert_eq!(b1, v1);
+                assert_eq!(b2, v2);
+                assert_eq!(v.len(), b_5_5.len());
+            }
+            {
+                let mut cursor_4_4 = b_4_4.cursor_at_mut(index);
+                let b1 = cursor_4_4.remove();
+                let b2 = cursor_4_4.remove();
+                assert_eq!(b1, v1);
+                assert_eq!(b2, v2);
+                assert_eq!(v.len(), b_4_4.len());
+            }
+            b_4_4.assert_invariants();
+            b_5_5.assert_invariants();
+        }
+    }
+
+    #[test]
+    fn test_random_cursor_move_right() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let mut b = BVec::<i32>::new();
+        let n = 1000;
+
+        for x in 0..n {
+            b.push_back(x);
+        }
+
+        for _ in 0..n {
+            let (start, end) = (rng.gen_range(0..b.len()), rng.gen_range(0..b.len()));
+
+            let mut c = b.cursor_at_mut(start);
+            c.move_(end as isize - start as isize);
+            assert_eq!(Some(&(end as i32)), c.get());
+        }
+    }
+
+    #[test]
+    fn test_cursor_move_past_the_end() {
+        let mut b = BVec::new();
+        b.extend(0..1000);
+
+        let mut c = b.cursor_at_mut(10);
+        c.move_(990);
+        assert_eq!(c.index(), 1000);
+        assert_eq!(c.get(), None);
+        c.move_(-1);
+        assert_eq!(c.get(), Some(&999));
+        c.move_(-999);
+        assert_eq!(c.get(), Some(&0));
+        c.move_(1000);
+        assert!(!c.is_inbounds());
+        c.move_(-500);
+        assert_eq!(c.get(), Some(&500));
+    }
+
+    #[test]
+    fn test_cursor_index() {
+        let b: BVec<i32> = (0..100).collect();
+
+        let mut c = b.cursor_at(95);
+        for i in 95..100 {
+            assert_eq!(c.index(), i);
+            c.move_next();
+        }
+        assert_eq!(c.index(), b.len());
+        assert_eq!(c.get(), None);
+        c.move_(-100);
+        assert_eq!(c.index(), 0);
+        c.move_(100);
+        assert_eq!(c.index(), 100);
+        assert_eq!(c.get(), None);
+        c.move_prev();
+        assert_eq!((c.index(), c.get()), (99, Some(&99)));
+
+        let empty = BVec::<i32>::new();
+        let mut c = empty.cursor_at(0);
+        c.move_(0);
+        assert_eq!((c.index(), c.get()), (0, None));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cursor_move_out_of_bounds() {
+        let mut b = BVec::new();
+        b.extend(0..1000);
+        b.cursor_at_mut(10).move_(991);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cursor_move_before_start() {
+        let mut b = BVec::new();
+        b.extend(0..1000);
+        b.cursor_at_mut(10).move_(-11);
+    }
+
+    #[test]
+    fn test_random_small_nodes() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let mut b = BVec::<i32, 4, 8>::new_with_params();
+        let mut v = Vec::new();
+
+        for x in 0..5000 {
+            match rng.gen_range(0..10) {
+                0..=4 => {
+                    let index = rng.gen_range(0..=v.len());
+                    b.insert(index, x);
+                    v.insert(index, x);
+                }
+                5..=7 if !v.is_empty() => {
+                    let index = rng.gen_range(0..v.len());
+                    assert_eq!(b.remove(index), v.remove(index));
+                }
+                8 => {
+                    let at = rng.gen_range(0..=v.len());
+                    let mut tail = b.split_off(at);
+                    let v_tail = v.split_off(at);
+                    assert!(tail.iter().eq(v_tail.iter()));
+                    tail.append(&mut b);
+                    b = tail;
+                    v = v_tail.into_iter().chain(v).collect();
+                }
+                _ => {
+                    b.reverse();
+                    v.reverse();
+                }
+            }
+            assert_eq!(b.len(), v.len());
+            b.assert_invariants();
+        }
+        assert!(b.iter().eq(v.iter()));
+    }
+
+    #[test]
+    fn test_random_cursor_get() {
+        let mut b_4_4 = BVec::<i32, 4, 16>::new_with_params();
+        let mut b_5_5 = BVec::<i32, 8, 20>::new_with_params();
+        let n = 1000;
+
+        for x in 0..n as i32 {
+            b_4_4.push_back(x);
+            b_5_5.push_back(x);
+        }
+
+        for i in 0..n {
+            let x = *b_4_4.get(i).unwrap();
+            let y = *b_5_5.get(i).unwrap();
+
+            assert_eq!(x, y);
+            assert_eq!(x, *b_4_4.cursor_at_mut(i).get().unwrap());
+            assert_eq!(y, *b_5_5.cursor_at_mut(i).get().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_bvec_iter() {
+        let n = 1000;
+        let mut b = BVec::new();
+        for x in 0..n {
+            b.push_back(x);
+        }
+
+        assert!(b.iter().copied().eq(0..n));
+    }
+
+    #[test]
+    fn test_bvec_iter_size_hint() {
+        let mut b = BVec::new();
+        let mut it = b.iter();
+        assert_eq!(it.len(), 0);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+
+        b.extend(0..1000);
+        let mut it = b.iter();
+        assert_eq!(it.size_hint(), (1000, Some(1000)));
+        it.next();
+        assert_eq!(it.len(), 999);
+        let mut it = it.skip(998);
+        assert_eq!(it.next(), Some(&999));
+        assert_eq!(it.len(), 0);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_bvec_iter_nth() {
+        let mut b = BVec::new();
+        b.extend(0..10_000);
+
+        let mut it = b.iter();
+        assert_eq!(it.nth(1), Some(&1));
+        assert_eq!(it.nth(5_000), Some(&5_002));
+        assert_eq!(it.next(), Some(&5_003));
+        assert_eq!(it.len(), 4_996);
+        assert_eq!(it.nth(4_995), Some(&9_999));
+        assert_eq!(it.next(), None);
+
+        let mut it = b.iter();
+        assert_eq!(it.nth(10_000), None);
+        assert_eq!(it.next(), None);
+        assert!(b.iter().step_by(7).copied().eq((0..10_000).step_by(7)));
+    }
+
+    #[test]
+    fn test_bvec_iter_mut() {
+        let mut b = BVec::new();
+        b.extend(0..10_000);
+
+        for x in b.iter_mut() {
+            *x *= 2;
+        }
+        assert!(b.iter().copied().eq((0..10_000).map(|x| x * 2)));
+
+        let mut it = b.iter_mut();
+        assert_eq!(it.len(), 10_000);
+        *it.nth(5_000).unwrap() = -1;
+        assert_eq!(b.get(5_000), Some(&-1));
+
+        assert_eq!(BVec::<i32>::new().iter_mut().next(), None);
+    }
+
+    #[test]
+    fn test_bvec_clone() {
+        let mut b = BVec::new();
+        b.extend(0..1000);
+
+        let mut c = b.clone();
+        assert_eq!(c.len(), 1000);
+        assert!(c.iter().eq(b.iter()));
+
+        c[500] = -1;
+        c.push_back(1000);
+        c.remove(0);
+        assert!(b.iter().copied().eq(0..1000));
+        assert_eq!(c.len(), 1000);
+        assert_eq!(c[499], -1);
+
+        assert!(BVec::<i32>::new().clone().is_empty());
+    }
+
+    #[test]
+    fn test_bvec_ord() {
+        use alloc::vec::Vec;
+
+        let from_vec = |v: &Vec<i32>| {
+            let mut b = BVec::new();
+            b.extend(v.iter().copied());
+            b
+        };
+        let long: Vec<i32> = (0..1000).collect();
+        let mut changed = long.clone();
+        changed[700] = -1;
+        let pairs = [
+            (Vec::new(), Vec::new()),
+            (Vec::new(), alloc::vec![0]),
+            (alloc::vec![1, 2, 3], alloc::vec![1, 2, 4]),
+            (alloc::vec![1, 2, 3], alloc::vec![1, 2]),
+            (long[..999].to_vec(), long.clone()),
+            (long.clone(), long.clone()),
+            (long.clone(), changed),
+        ];
+
+        for (x, y) in &pairs {
+            let (a, b) = (from_vec(x), from_vec(y));
+            assert_eq!(a.cmp(&b), x.cmp(y));
+            assert_eq!(b.cmp(&a), y.cmp(x));
+            assert_eq!(a.partial_cmp(&b), x.partial_cmp(y));
+            assert_eq!(a == b, x == y);
+        }
+
+        let mut a = BVec::new();
+        a.extend([1.0, f64::NAN]);
+        assert_eq!(a.partial_cmp(&a.clone()), None);
+    }
+
+    #[test]
+    fn test_bvec_from_iter() {
+        for n in [0, 1, 100, 10_000] {
+            let b: BVec<i32> = (0..n).collect();
+            assert_eq!(b.len(), n as usize);
+            assert!(b.iter().copied().eq(0..n));
+        }
+
+        let mut b: BVec<i32> = (0..10_000).collect();
+        for x in (0..10_000).rev().step_by(3) {
+            b.remove(x);
+        }
+        b.insert(0, -1);
+        assert_eq!(b.len(), 6_667);
+        assert_eq!(b[0], -1);
+    }
+
+    #[test]
+    fn test_bvec_to_vec() {
+        use alloc::vec::Vec;
+        use core::cell::Cell;
+
+        let b: BVec<i32> = (0..1000).collect();
+        assert_eq!(b.to_vec(), (0..1000).collect::<Vec<_>>());
+        assert_eq!(b.into_vec(), (0..1000).collect::<Vec<_>>());
+        assert!(BVec::<i32>::new().into_vec().is_empty());
+
+        struct D<'a>(usize, &'a Cell<usize>);
+        impl Drop for D<'_> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let b: BVec<D> = (0..1000).map(|x| D(x, &drops)).collect();
+        let v = b.into_vec();
+        assert_eq!(drops.get(), 0);
+        assert!(v.iter().map(|d| d.0).eq(0..1000));
+        drop(v);
+        assert_eq!(drops.get(), 1000);
+
+        let b: BVec<D> = (0..1000).map(|x| D(x, &drops)).collect();
+        let mut it = b.into_iter();
+        assert_eq!(it.size_hint(), (1000, Some(1000)));
+        assert_eq!(it.nth(10).map(|d| d.0), Some(10));
+        drop(it);
+        assert_eq!(drops.get(), 2000);
+    }
+
+    #[test]
+    fn test_contiguous() {
+        use alloc::string::{String, ToString};
+
+        let v: Vec<String> = (0..1000).map(|x| x.to_string()).collect();
+        let b: BVec<String> = v.iter().cloned().collect();
+        let refs = b.as_contiguous();
+        assert_eq!(refs.len(), 1000);
+        assert!(refs.into_iter().eq(&v));
+        assert_eq!(b.into_contiguous(), v);
+    }
+
+    #[test]
+    fn test_position() {
+        let b = (0..1000).collect::<BVec<i32, 4, 16>>();
+        assert_eq!(b.position(|x| x % 2 == 0), Some(0));
+        assert_eq!(b.rposition(|x| x % 2 == 0), Some(998));
+        assert_eq!(b.position(|&x| x > 500), Some(501));
+        assert_eq!(b.rposition(|&x| x < 3), Some(2));
+        assert_eq!(b.position(|&x| x < 0), None);
+        assert_eq!(b.rposition(|&x| x < 0), None);
+        assert_eq!(BVec::<i32>::new().rposition(|_| true), None);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut b: BVec<i32> = (0..1000).collect();
+        b.truncate(2000);
+        assert_eq!(b.len(), 1000);
+        b.truncate(1000);
+        assert_eq!(b.len(), 1000);
+        b.truncate(321);
+        assert!(b.iter().copied().eq(0..321));
+        b.push_back(321);
+        assert!(b.iter().copied().eq(0..322));
+        b.truncate(0);
+        assert!(b.is_empty());
+        b.push_back(1);
+        assert!(b.iter().copied().eq(1..2));
+    }
+
+    #[test]
+    fn test_retain_count() {
+        let mut b: BVec<i32, 4, 16> = (0..1_000).collect();
+        let old_len = b.len();
+        let removed = b.retain_count(|x| x % 3 == 0);
+        assert_eq!(removed, old_len - b.len());
+        assert_eq!(removed, 666);
+        assert_eq!(b.retain_count(|_| true), 0);
+        assert_eq!(b.retain_count(|_| false), 334);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_retain_mut() {
+        let mut v: Vec<i32> = (0..1000).collect();
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..1000);
+
+        let f = |x: &mut i32| {
+            *x += 1;
+            *x % 5 != 0
+        };
+        v.retain_mut(f);
+        b.retain_mut(f);
+        assert!(b.iter().eq(&v));
+        assert_eq!(b.len(), 800);
+
+        b.retain_mut(|_| false);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_chunks() {
+        let v: Vec<i32> = (0..1000).collect();
+        let b: BVec<i32, 4, 16> = v.iter().copied().collect();
+
+        for size in [1, 3, 7, 100, 1000, 2000] {
+            let chunks = b.chunks(size);
+            assert_eq!(chunks.len(), v.chunks(size).len());
+            assert!(chunks.eq(v.chunks(size).map(|c| c.iter().collect::<Vec<_>>())));
+            let rchunks = b.rchunks(size);
+            assert_eq!(rchunks.len(), v.rchunks(size).len());
+            assert!(rchunks.eq(v.rchunks(size).map(|c| c.iter().collect::<Vec<_>>())));
+        }
+
+        assert_eq!(b.chunks(300).last().unwrap().len(), 100);
+        assert_eq!(b.rchunks(300).last().unwrap().len(), 100);
+        assert_eq!(BVec::<i32>::new().chunks(5).next(), None);
+        assert_eq!(BVec::<i32>::new().rchunks(5).next(), None);
+    }
+
+    #[test]
+    fn test_chunks_mut() {
+        let mut b: BVec<i32, 4, 16> = (1..=1000).collect();
+
+        let mut chunks = b.chunks_mut(7);
+        assert_eq!(chunks.len(), 143);
+        let first = chunks.next().unwrap();
+        let second = chunks.next().unwrap();
+        assert_eq!(first.len(), 7);
+        assert_eq!(*second[0], 8);
+        assert_eq!(chunks.last().unwrap().len(), 6);
+
+        for chunk in b.chunks_mut(7) {
+            assert!(chunk.len() <= 7);
+            for x in chunk {
+                *x = 0;
+            }
+        }
+        assert!(b.iter().all(|&x| x == 0));
+        assert_eq!(b.len(), 1000);
+        assert_eq!(BVec::<i32>::new().chunks_mut(5).next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunks_mut_zero_size() {
+        let mut b: BVec<i32> = (0..10).collect();
+        let _ = b.chunks_mut(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunks_zero_size() {
+        let b: BVec<i32> = (0..10).collect();
+        let _ = b.chunks(0);
+    }
+
+    #[test]
+    fn test_windows() {
+        let v: Vec<i32> = (0..50).collect();
+        let b: BVec<i32, 4, 16> = v.iter().copied().collect();
+
+        for size in [1, 2, 5, 49, 50, 51] {
+            let mut windows = b.windows(size);
+            assert_eq!(windows.len(), v.windows(size).len());
+            windows.next();
+            assert_eq!(windows.len(), v.windows(size).len().saturating_sub(1));
+            assert!(b
+                .windows(size)
+                .eq(v.windows(size).map(|w| w.iter().collect::<Vec<_>>())));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_windows_zero_size() {
+        let b: BVec<i32> = (0..10).collect();
+        let _ = b.windows(0);
+    }
+
+    #[test]
+    fn test_split_at_mut() {
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..1000);
+
+        for mid in [0, 1, 500, 999, 1000] {
+            let (head, tail) = b.split_at_mut(mid);
+            assert_eq!(head.len(), mid);
+            assert_eq!(tail.len(), 1000 - mid);
+            for (x, y) in head.zip(tail) {
+                mem::swap(x, y);
+            }
+            let (head, tail) = b.split_at_mut(mid);
+            for (x, y) in head.zip(tail) {
+                mem::swap(x, y);
+            }
+            assert!(b.iter().copied().eq(0..1000));
+        }
+
+        let (head, tail) = b.split_at_mut(300);
+        head.for_each(|x| *x = -*x);
+        tail.for_each(|x| *x *= 2);
+        assert!(b
+            .iter()
+            .copied()
+            .eq((0..300).map(|x| -x).chain((300..1000).map(|x| 2 * x))));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_at_mut_out_of_bounds() {
+        let mut b: BVec<i32> = (0..10).collect();
+        let _ = b.split_at_mut(11);
+    }
+
+    #[test]
+    fn test_swap() {
+        use alloc::vec::Vec;
+
+        let mut b: BVec<i32> = (0..1000).collect();
+        let mut v: Vec<i32> = (0..1000).collect();
+        for (i, j) in [
+            (0, 1),
+            (1, 0),
+            (5, 5),
+            (0, 999),
+            (998, 3),
+            (500, 501),
+            (10, 700),
+        ] {
+            b.swap(i, j);
+            v.swap(i, j);
+            assert!(b.iter().eq(v.iter()));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_swap_out_of_bounds() {
+        let mut b: BVec<i32> = (0..10).collect();
+        b.swap(3, 10);
+    }
+
+    #[test]
+    fn test_reverse() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        for n in [0, 1, 2, 100, 1000] {
+            let mut b: BVec<i32> = (0..n).collect();
+            let mut v: Vec<i32> = (0..n).collect();
+            b.reverse();
+            v.reverse();
+            assert!(b.iter().eq(v.iter()));
+
+            // make sure the lengths and parent links are still correct
+            for x in 0..1000 {
+                let index = rng.gen_range(0..=v.len());
+                b.insert(index, x);
+                v.insert(index, x);
+            }
+            while !v.is_empty() {
+                let index = rng.gen_range(0..v.len());
+                assert_eq!(v.remove(index), b.remove(index));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotate() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut b = BVec::<i32>::new();
+        b.rotate_left(0);
+        b.rotate_right(0);
+        assert!(b.is_empty());
+
+        let mut b: BVec<i32> = (0..1000).collect();
+        let mut v: Vec<i32> = (0..1000).collect();
+        for k in [0, 1000] {
+            b.rotate_left(k);
+            b.rotate_right(k);
+        }
+        assert!(b.iter().eq(v.iter()));
+
+        for _ in 0..100 {
+            let k = rng.gen_range(0..=v.len());
+            if rng.gen() {
+                b.rotate_left(k);
+                v.rotate_left(k);
+            } else {
+                b.rotate_right(k);
+                v.rotate_right(k);
+            }
+            assert!(b.iter().eq(v.iter()));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rotate_out_of_bounds() {
+        let mut b: BVec<i32> = (0..10).collect();
+        b.rotate_left(11);
+    }
+
+    #[test]
+    fn test_binary_search() {
+        use alloc::vec::Vec;
+
+        let v: Vec<i32> = (0..1000).map(|x| x * 2).collect();
+        let b: BVec<i32> = v.iter().copied().collect();
+
+        for x in -2..2002 {
+            assert_eq!(b.binary_search(&x), v.binary_search(&x));
+        }
+        assert_eq!(b.binary_search(&-5), Err(0));
+        assert_eq!(b.binary_search(&5000), Err(1000));
+        assert_eq!(b.binary_search_by_key(&500, |x| x / 2), Ok(500));
+        assert_eq!(BVec::new().binary_search(&1), Err(0));
+
+        let v: Vec<i32> = (0..100_000).map(|x| x * 2).collect();
+        let b: BVec<i32> = v.iter().copied().collect();
+        for x in (-1..200_001).step_by(97) {
+            assert_eq!(b.binary_search(&x), v.binary_search(&x));
+        }
+
+        let dups: BVec<i32> = (0..1000).map(|x| x / 100).collect();
+        for x in 0..10 {
+            let index = dups.binary_search(&x).unwrap();
+            assert_eq!(dups[index], x);
+        }
+        assert_eq!(dups.binary_search(&10), Err(1000));
+        assert_eq!(
+            dups.binary_search_by(|x| x.cmp(&3).then(Ordering::Less)),
+            Err(400)
+        );
+    }
+
+    #[test]
+    fn test_get_many_mut() {
+        let mut b: BVec<i32> = (0..1000).collect();
+
+        let [x, y] = b.get_many_mut([10, 900]).unwrap();
+        mem::swap(x, y);
+        assert_eq!(b[10], 900);
+        assert_eq!(b[900], 10);
+
+        let [x, y, z] = b.get_many_mut([999, 0, 500]).unwrap();
+        *x += 1;
+        *y += 1;
+        *z += 1;
+        assert_eq!((b[999], b[0], b[500]), (1000, 1, 501));
+
+        assert!(b.get_many_mut([]).is_some());
+        assert!(b.get_many_mut([3, 5, 3]).is_none());
+        assert!(b.get_many_mut([7, 7]).is_none());
+        assert!(b.get_many_mut([0, 1000]).is_none());
+    }
+
+    #[test]
+    fn test_swap_remove() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut b: BVec<i32> = (0..1000).collect();
+        let mut v: Vec<i32> = (0..1000).collect();
+        assert_eq!(b.swap_remove(999), v.swap_remove(999));
+        while !v.is_empty() {
+            let index = rng.gen_range(0..v.len());
+            assert_eq!(b.swap_remove(index), v.swap_remove(index));
+            assert!(b.iter().eq(v.iter()));
+        }
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_swap_remove_out_of_bounds() {
+        let mut b: BVec<i32> = (0..10).collect();
+        b.swap_remove(10);
+    }
+
+    #[test]
+    fn test_retain() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut b: BVec<i32> = (0..1000).collect();
+        b.retain(|x| x % 2 == 0);
+        assert!(b.iter().copied().eq((0..1000).step_by(2)));
+        b.retain(|_| true);
+        assert_eq!(b.len(), 500);
+
+        let mut b: BVec<i32> = (0..1000).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            b.retain(|&x| {
+                assert!(x != 600);
+                x % 3 == 0
+            });
+        }));
+        assert!(result.is_err());
+        assert!(b.iter().copied().eq((0..600).step_by(3).chain(600..1000)));
+        b.retain(|_| false);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_bvec_extend() {
+        let n = 500;
+        let mut b = BVec::new();
+        for x in 0..n {
+            b.push_back(x);
+        }
+
+        b.extend(n..2 * n);
+
+        assert!(b.iter().copied().eq(0..2 * n));
+    }
+
+    #[test]
+    fn test_append_differing_heights() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let sizes = [0, 1, 2, 7, 33, 100, 1000, 5000];
+
+        for &n in &sizes {
+            for &m in &sizes {
+                let mut a = BVec::new();
+                let mut b = BVec::new();
+                a.extend(0..n);
+                b.extend(n..n + m);
+
+                a.append(&mut b);
+                assert!(b.is_empty());
+                assert_eq!(a.len(), n + m);
+                assert!(a.iter().copied().eq(0..n + m));
+
+                // make sure the seam is structurally sound
+                let mut v = Vec::from_iter(0..n + m);
+                while !v.is_empty() {
+                    let index = rng.gen_range(0..v.len());
+                    assert_eq!(v.remove(index), a.remove(index));
+                }
+                assert!(a.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_extend_ref() {
+        let mut b = BVec::<i32>::new();
+        b.extend(&[1, 2, 3]);
+        b.extend([4, 5].iter());
+        let v: Vec<i32> = (6..100).collect();
+        b.extend(&v);
+        assert!(b.iter().copied().eq(1..100));
+    }
+
+    #[test]
+    fn test_extend_from_slice() {
+        let mut v: Vec<i32> = Vec::new();
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        for len in [0, 1, 5, 100, 2000, 3] {
+            let slice: Vec<i32> = (0..len).map(|x| x * len).collect();
+            v.extend_from_slice(&slice);
+            b.extend_from_slice(&slice);
+            assert!(b.iter().eq(&v));
+        }
+    }
+
+    #[test]
+    fn test_insert_slice() {
+        let mut v: Vec<i32> = (0..300).collect();
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..300);
+
+        let slice: Vec<i32> = (-100..0).collect();
+        for index in [0, 150, 400, 600, 0, 1] {
+            v.splice(index..index, slice.iter().copied());
+            b.insert_slice(index, &slice);
+            assert!(b.iter().eq(&v));
+        }
+        b.insert_slice(b.len(), &[]);
+        assert!(b.iter().eq(&v));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_slice_out_of_bounds() {
+        let mut b: BVec<i32> = (0..10).collect();
+        b.insert_slice(11, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_append_empty() {
+        let mut a: BVec<i32> = (0..1000).collect();
+        let mut b = BVec::new();
+
+        a.append(&mut b);
+        assert!(a.iter().copied().eq(0..1000));
+        assert!(b.is_empty());
+
+        b.append(&mut a);
+        assert!(b.iter().copied().eq(0..1000));
+        assert!(a.is_empty());
+
+        a.append(&mut BVec::new());
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    fn test_append_large() {
+        let mut a: BVec<i32> = (0..100_000).collect();
+        let mut b: BVec<i32> = (100_000..250_000).collect();
+
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(a.len(), 250_000);
+        assert!(a.iter().copied().eq(0..250_000));
+
+        let c = a.split_off(100_000);
+        a.append(&mut c.clone());
+        a.append(&mut c.clone());
+        assert!(a.iter().copied().eq((0..250_000).chain(100_000..250_000)));
+        assert_eq!(a.drain(..).count(), 400_000);
+    }
+
+    #[test]
+    fn test_split_off() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let n = 1000;
+
+        for at in (0..=n).step_by(7).chain([1, n - 1, n]) {
+            let mut a: BVec<i32> = (0..n as i32).collect();
+            let mut b = a.split_off(at);
+            assert_eq!(a.len(), at);
+            assert_eq!(b.len(), n - at);
+            assert!(a.iter().copied().eq(0..at as i32));
+            assert!(b.iter().copied().eq(at as i32..n as i32));
+
+            // make sure both halves are structurally sound
+            let mut v = Vec::from_iter(at as i32..n as i32);
+            while !v.is_empty() {
+                let index = rng.gen_range(0..v.len());
+                assert_eq!(v.remove(index), b.remove(index));
+            }
+            for x in 0..1000 {
+                a.insert(rng.gen_range(0..=a.len()), x);
+            }
+            assert_eq!(a.len(), at + 1000);
+        }
+    }
+
+    #[test]
+    fn test_cursor_split() {
+        for len in [0_usize, 1, 7, 100, 1000] {
+            for at in [0, 1, len / 3, len / 2, len.saturating_sub(1), len] {
+                if at > len {
+                    continue;
+                }
+                let mut a = BVec::<usize, 4, 16>::new_with_params();
+                a.extend(0..len);
+                let mut b = a.clone();
+                let a_tail = a.split_off(at);
+                let b_tail = b.cursor_at_mut(at).split();
+                assert_eq!(a, b);
+                assert_eq!(a_tail, b_tail);
+                assert_eq!(b.len(), at);
+                assert!(b_tail.iter().copied().eq(at..len));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_off_out_of_bounds() {
+        let mut b: BVec<i32> = (0..10).collect();
+        let _ = b.split_off(11);
+    }
+
+    #[test]
+    fn test_append_then_insert() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        let mut v = Vec::new();
+        let mut b = BVec::new();
+        for x in 0..100 {
+            let mut other = BVec::new();
+            other.extend(x * 37..(x + 1) * 37);
+            v.extend(x * 37..(x + 1) * 37);
+            if rng.gen() {
+                b.append(&mut other);
+            } else {
+                other.append(&mut b);
+                b = other;
+                v.rotate_right(37);
+            }
+        }
+
+        for x in 0..1000 {
+            let index = rng.gen_range(0..=v.len());
+            v.insert(index, x);
+            b.insert(index, x);
+        }
+        assert_eq!(v, b.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_builder() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+
+        for n in [0, 1, 2, 63, 64, 65, 1000, 5000, 70000] {
+            let mut builder = BVecBuilder::new();
+            for x in 0..n {
+                builder.push(x);
+            }
+            assert_eq!(builder.len(), n);
+
+            let mut b = builder.finish();
+            assert_eq!(b.len(), n);
+            assert!(b.iter().copied().eq(0..n));
+
+            let mut v = Vec::from_iter(0..n);
+            for x in 0..1000 {
+                let index = rng.gen_range(0..=v.len());
+                v.insert(index, x);
+                b.insert(index, x);
+            }
+            while !v.is_empty() {
+                let index = rng.gen_range(0..v.len());
+                assert_eq!(v.remove(index), b.remove(index));
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_sparse() {
+        use alloc::vec::Vec;
+
+        let edits = [(0, 1), (3, 2), (500, 3), (999, 4)];
+        let b = BVec::<i32>::from_sparse(1000, 0, edits);
+
+        let mut v = alloc::vec![0; 1000];
+        for (i, x) in edits {
+            v[i] = x;
+        }
+        assert_eq!(v, b.iter().copied().collect::<Vec<_>>());
+
+        assert!(BVec::<i32>::from_sparse(0, 0, []).is_empty());
+        assert!(BVec::<i32>::from_sparse(100, 7, []).iter().all(|&x| x == 7));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn test_from_sparse_unsorted() {
+        let _ = BVec::<i32>::from_sparse(10, 0, [(5, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn test_find_cursor() {
+        let mut b = BVec::new();
+        b.extend(0..1000);
+
+        let mut c = b.find_cursor(|&x| x >= 500).unwrap();
+        assert_eq!(c.index(), 500);
+        assert_eq!(c.get(), Some(&500));
+        c.move_(-100);
+        assert_eq!(c.get(), Some(&400));
+        assert!(b.find_cursor(|&x| x > 1000).is_none());
+
+        let mut c = b.find_cursor_mut(|&x| x % 300 == 299).unwrap();
+        assert_eq!(c.remove(), 299);
+        c.insert(-1);
+        assert_eq!(b[299], -1);
+        assert!(BVec::<i32>::new().find_cursor_mut(|_| true).is_none());
+    }
+
+    #[test]
+    fn test_get_sorted() {
+        use alloc::vec::Vec;
+
+        let mut b = BVec::new();
+        b.extend(0..10_000);
+
+        let indices = Vec::from_iter((0..10_000).step_by(37));
+        let refs = b.get_sorted(&indices).unwrap();
+        assert!(refs.into_iter().copied().eq(indices.iter().copied()));
+
+        assert_eq!(b.get_sorted(&[]), Some(Vec::new()));
+        assert_eq!(b.get_sorted(&[9_999]), Some(alloc::vec![&9_999]));
+        assert_eq!(b.get_sorted(&[5, 10_000]), None);
+        assert_eq!(BVec::<i32>::new().get_sorted(&[0]), None);
+    }
+
+    #[test]
+    fn test_drain() {
+        use alloc::vec::Vec;
+
+        let mut b = BVec::new();
+        b.extend(0..10_000);
+        let mut v: Vec<i32> = (0..10_000).collect();
+
+        assert!(b.drain(100..2_000).eq(v.drain(100..2_000)));
+        assert!(b.iter().eq(v.iter()));
+
+        // Dropping a partially consumed `Drain` still removes the whole range.
+        let mut d = b.drain(5_000..=6_000);
+        assert_eq!(d.next(), Some(6_900));
+        drop(d);
+        v.drain(5_000..=6_000);
+        assert!(b.iter().eq(v.iter()));
+
+        b.drain(..);
+        assert!(b.is_empty());
+        assert_eq!(b.drain(0..0).next(), None);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..1000);
+
+        assert!(b.range(250..750).copied().eq(250..750));
+        assert_eq!(b.range(250..750).len(), 500);
+        assert!(b.range(..=10).copied().eq(0..=10));
+        assert!(b.range(990..).copied().eq(990..1000));
+        assert_eq!(b.range(1000..).next(), None);
+        assert_eq!(b.range(500..500).next(), None);
+
+        for x in b.range_mut(250..750) {
+            *x = -*x;
+        }
+        assert!(b
+            .iter()
+            .copied()
+            .eq((0..250).chain((250..750).map(|x| -x)).chain(750..1000)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_out_of_bounds() {
+        let b: BVec<i32> = (0..10).collect();
+        let _ = b.range(5..11);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_mut_start_after_end() {
+        let mut b: BVec<i32> = (0..10).collect();
+        #[allow(clippy::reversed_empty_ranges)]
+        let _ = b.range_mut(6..5);
+    }
+
+    #[test]
+    fn test_splice() {
+        let mut v: Vec<i32> = (0..500).collect();
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..500);
+
+        let removed: Vec<_> = b.splice(100..300, 1000..1050).collect();
+        let expected: Vec<_> = v.splice(100..300, 1000..1050).collect();
+        assert_eq!(removed, expected);
+        assert!(b.iter().eq(&v));
+
+        let mut splice = b.splice(10..20, [-1; 30]);
+        assert_eq!(splice.next(), Some(10));
+        drop(splice);
+        v.splice(10..20, [-1; 30]);
+        assert!(b.iter().eq(&v));
+
+        drop(b.splice(.., []));
+        assert!(b.is_empty());
+        drop(b.splice(.., 0..10));
+        assert!(b.iter().copied().eq(0..10));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_drain_start_after_end() {
+        let mut b = BVec::new();
+        b.extend(0..10);
+        #[allow(clippy::reversed_empty_ranges)]
+        b.drain(5..4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_drain_end_out_of_bounds() {
+        let mut b = BVec::new();
+        b.extend(0..10);
+        b.drain(5..11);
+    }
+
+    #[test]
+    fn test_drain_panicking_drop() {
+        use core::cell::Cell;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        struct D<'a>(i32, &'a Cell<usize>);
+        impl Drop for D<'_> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+                assert!(self.0 != 500, "boom");
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut b = BVec::new();
+        b.extend((0..1_000).map(|x| D(x, &drops)));
+        let result = catch_unwind(AssertUnwindSafe(|| drop(b.drain(100..900))));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 800);
+        assert_eq!(b.len(), 200);
+        assert!(b.iter().map(|d| d.0).eq((0..100).chain(900..1_000)));
+    }
+
+    #[test]
+    fn test_drain_panicking_drop_partially_consumed() {
+        use core::cell::Cell;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        struct D<'a>(i32, &'a Cell<usize>);
+        impl Drop for D<'_> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+                assert!(self.0 != 500, "boom");
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut b = BVec::<D, 4, 16>::new_with_params();
+        b.extend((0..1_000).map(|x| D(x, &drops)));
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut drain = b.drain(20..980);
+            for d in drain.by_ref().take(10) {
+                mem::forget(d);
+            }
+            drop(drain);
+        }));
+        assert!(result.is_err());
+        // Everything but the forgotten values is dropped exactly once.
+        assert_eq!(drops.get(), 950);
+        assert_eq!(b.len(), 40);
+        assert!(b.iter().map(|d| d.0).eq((0..20).chain(980..1_000)));
+
+        b.insert(20, D(-1, &drops));
+        assert_eq!(b.remove(0).0, 0);
+        assert!(b
+            .iter()
+            .map(|d| d.0)
+            .eq((1..20).chain([-1]).chain(980..1_000)));
+    }
+
+    #[test]
+    fn test_drain_double_ended() {
+        use alloc::vec::Vec;
+
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..1_000);
+        let mut drain = b.drain(100..901);
+        let mut front = 100;
+        let mut back = 900;
+        while drain.len() > 0 {
+            if drain.len().is_multiple_of(2) {
+                assert_eq!(drain.next(), Some(front));
+                front += 1;
+            } else {
+                assert_eq!(drain.next_back(), Some(back));
+                back -= 1;
+            }
+            assert_eq!(drain.len(), (back + 1 - front) as usize);
+        }
+        assert_eq!(front, back + 1);
+        assert_eq!(drain.next(), None);
+        assert_eq!(drain.next_back(), None);
+        drop(drain);
+
+        b.assert_invariants();
+        assert_eq!(
+            b.iter().copied().collect::<Vec<_>>(),
+            (0..100).chain(901..1_000).collect::<Vec<_>>()
+        );
+
+        let mut v: Vec<_> = (0..50).collect();
+        b = v.iter().copied().collect();
+        assert!(b.drain(10..40).rev().eq(v.drain(10..40).rev()));
+        assert!(b.iter().eq(v.iter()));
+    }
+
+    #[test]
+    fn test_map_in_place() {
+        use alloc::{rc::Rc, vec::Vec};
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let counter = Rc::new(());
+        let mut b: BVec<_, 4, 16> = (0..1_000).map(|x| (x, counter.clone())).collect();
+        b.map_in_place(|(x, rc)| (x + 1, rc));
+        assert!(b.iter().map(|(x, _)| *x).eq(1..=1_000));
+        assert_eq!(Rc::strong_count(&counter), 1_001);
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            b.map_in_place(|(x, rc)| {
+                assert!(x != 500, "boom");
+                (x * 2, rc)
+            });
+        }));
+        assert!(result.is_err());
+        b.assert_invariants();
+        assert_eq!(Rc::strong_count(&counter), 1_000);
+        assert_eq!(
+            b.iter().map(|(x, _)| *x).collect::<Vec<_>>(),
+            (1..500)
+                .map(|x| x * 2)
+                .chain(501..=1_000)
+                .collect::<Vec<_>>()
+        );
+
+        drop(b);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_clear_panicking_drop() {
+        use core::cell::Cell;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        struct D<'a>(i32, &'a Cell<usize>);
+        impl Drop for D<'_> {
+            fn drop(&mut self) {
+                self.1.set(self.1.get() + 1);
+                assert!(self.0 != 500, "boom");
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut b = BVec::<D, 4, 16>::new_with_params();
+        b.extend((0..1_000).map(|x| D(x, &drops)));
+        let result = catch_unwind(AssertUnwindSafe(|| b.clear()));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 1_000);
+        assert!(b.is_empty());
+
+        b.push_back(D(0, &drops));
+        b.clear();
+        assert_eq!(drops.get(), 1_001);
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_bvec_move_empty_cursor() {
+        let mut b = BVec::<i32>::new();
+        let mut c = b.cursor_at_mut(0);
+        c.move_(0);
+    }
+
+    #[test]
+    fn test_bvec_hash() {
+        let n = 1000;
+        let mut b = BVec::new();
+        b.extend(0..n);
+
+        let v = alloc::vec::Vec::from_iter(0..n);
+
+        let mut v_hasher = std::collections::hash_map::DefaultHasher::new();
+        v.hash(&mut v_hasher);
+        let v_hash = v_hasher.finish();
+
+        let mut b_hasher = std::collections::hash_map::DefaultHasher::new();
+        b.hash(&mut b_hasher);
+        let b_hash = b_hasher.finish();
+
+        assert_eq!(v_hash, b_hash);
+    }
+
+    #[test]
+    fn test_bvec_hash_independent_of_shape() {
+        use alloc::vec::Vec;
+        use rand::{seq::SliceRandom, SeedableRng};
+
+        /// Records every call made to it, so that two values only hash
+        /// equally under every hasher if their records are equal.
+        #[derive(Default)]
+        struct RecordingHasher(Vec<Vec<u8>>);
+        impl Hasher for RecordingHasher {
+            fn finish(&self) -> u64 {
+                0
+            }
+            fn write(&mut self, bytes: &[u8]) {
+                self.0.push(bytes.to_vec());
+            }
+        }
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let n = 2_000;
+        let mut pushed = BVec::<u8, 4, 16>::new_with_params();
+        pushed.extend((0..n).map(|x| x as u8));
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.shuffle(&mut rng);
+        let mut inserted = BVec::<u8, 4, 16>::new_with_params();
+        for (i, &x) in order.iter().enumerate() {
+            let index = order[..i].iter().filter(|&&y| y < x).count();
+            inserted.insert(index, x as u8);
+        }
+        let collected: BVec<u8, 4, 16> = pushed.iter().copied().collect();
+
+        assert_eq!(pushed, inserted);
+        assert_eq!(pushed, collected);
+        assert_ne!(pushed.node_count(), inserted.node_count());
+
+        let record = |b: &BVec<u8, 4, 16>| {
+            let mut hasher = RecordingHasher::default();
+            b.hash(&mut hasher);
+            hasher.0
+        };
+        assert_eq!(record(&pushed), record(&inserted));
+        assert_eq!(record(&pushed), record(&collected));
+    }
+
+    #[test]
+    fn test_clone_from() {
+        use alloc::string::ToString;
+
+        let source: BVec<_, 4, 16> = (0..500).map(|x| x.to_string()).collect();
+        let mut b: BVec<_, 4, 16> = (0..100).map(|x| (x + 1_000).to_string()).collect();
+        b.clone_from(&source);
+        b.assert_invariants();
+        assert_eq!(b, source);
+
+        let node_count = b.node_count();
+        for _ in 0..3 {
+            b.clone_from(&source);
+            assert_eq!(b, source);
+            assert_eq!(b.node_count(), node_count);
+        }
+
+        let short: BVec<_, 4, 16> = (0..10).map(|x| x.to_string()).collect();
+        b.clone_from(&short);
+        b.assert_invariants();
+        assert_eq!(b, short);
+
+        b.clone_from(&BVec::new_with_params());
+        assert!(b.is_empty());
+        assert_eq!(
+            b.iter().collect::<Vec<_>>(),
+            Vec::<&alloc::string::String>::new()
+        );
+    }
+
+    #[test]
+    fn test_iter_advance_by() {
+        use core::num::NonZeroUsize;
+
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..1_000);
+
+        let mut iter = b.iter();
+        assert_eq!(iter.advance_by(0), Ok(()));
+        assert_eq!(iter.advance_by(300), Ok(()));
+        assert_eq!(iter.next(), Some(&300));
+        assert_eq!(iter.advance_back_by(99), Ok(()));
+        assert_eq!(iter.len(), 600);
+        assert_eq!(iter.advance_by(550), Ok(()));
+        assert!(iter.clone().copied().eq(851..901));
+        assert_eq!(
+            iter.advance_back_by(60),
+            Err(NonZeroUsize::new(10).unwrap())
+        );
+        assert_eq!(iter.next(), None);
+
+        let mut iter = b.iter();
+        assert_eq!(iter.advance_by(1_000), Ok(()));
+        assert_eq!(iter.next(), None);
+        let mut iter = b.iter();
+        assert_eq!(iter.advance_by(1_003), Err(NonZeroUsize::new(3).unwrap()));
+        assert_eq!(iter.next(), None);
+        assert_eq!(b.iter().nth(999), Some(&999));
+        assert_eq!(b.iter().nth(1_000), None);
+    }
+
+    #[test]
+    fn test_partition_point() {
+        let b: BVec<i32, 4, 16> = (0..1_000).collect();
+        for n in [0, 1, 2, 3, 4, 5, 17, 500, 998, 999, 1_000] {
+            assert_eq!(b.partition_point(|&x| x < n), n as usize);
+        }
+        assert_eq!(b.partition_point(|_| true), 1_000);
+        assert_eq!(b.partition_point(|_| false), 0);
+
+        let b: BVec<i32, 4, 16> = (0..1_000).map(|x| x / 10).collect();
+        assert_eq!(b.partition_point(|&x| x < 42), 420);
+        assert_eq!(b.partition_point(|&x| x <= 42), 430);
+        assert_eq!(BVec::<i32>::new().partition_point(|_| true), 0);
+    }
+
+    #[test]
+    fn test_split_first_last() {
+        let b: BVec<i32, 4, 16> = (0..100).collect();
+
+        let (first, mut rest) = b.split_first().unwrap();
+        assert_eq!(*first, 0);
+        for i in 1..100 {
+            assert_eq!(rest.get(), Some(&i));
+            rest.move_next();
+        }
+        assert!(!rest.is_inbounds());
+
+        let (last, mut rest) = b.split_last().unwrap();
+        assert_eq!(*last, 99);
+        for i in (1..99).rev() {
+            assert_eq!(rest.get(), Some(&i));
+            rest.move_prev();
+        }
+        assert_eq!(rest.get(), Some(&0));
+
+        let single: BVec<i32> = [7].into_iter().collect();
+        let (first, rest) = single.split_first().unwrap();
+        assert_eq!((*first, rest.get()), (7, None));
+        let (last, rest) = single.split_last().unwrap();
+        assert_eq!((*last, rest.get()), (7, None));
+
+        assert!(BVec::<i32>::new().split_first().is_none());
+        assert!(BVec::<i32>::new().split_last().is_none());
+    }
+
+    #[test]
+    fn test_cursor_remaining() {
+        let mut b: BVec<i32, 4, 16> = (0..20).collect();
+
+        let mut c = b.cursor_at(15);
+        for i in 15..20 {
+            assert_eq!(c.remaining(), 20 - i);
+            assert!(!c.is_past_end());
+            c.move_next();
+        }
+        assert_eq!(c.remaining(), 0);
+        assert!(c.is_past_end());
+
+        let mut c = b.cursor_at_mut(17);
+        for i in 17..20 {
+            assert_eq!(c.remaining(), 20 - i);
+            assert!(!c.is_past_end());
+            c.move_next();
+        }
+        assert_eq!(c.remaining(), 0);
+        assert!(c.is_past_end());
+        c.insert(20);
+        assert_eq!(c.remaining(), 1);
+        assert!(!c.is_past_end());
+
+        let mut empty = BVec::<i32>::new();
+        assert!(empty.cursor_front().is_past_end());
+        assert_eq!(empty.cursor_front_mut().remaining(), 0);
+    }
+
+    #[test]
+    fn test_vec_deque_conversions() {
+        use alloc::collections::VecDeque;
+
+        let mut d: VecDeque<i32> = (0..500).collect();
+        d.rotate_left(123);
+        let b = BVec::<i32, 4, 16>::from(d.clone());
+        b.assert_invariants();
+        assert!(b.iter().eq(d.iter()));
+        assert_eq!(VecDeque::from(b), d);
+
+        let b = BVec::<i32>::from(VecDeque::new());
+        assert!(b.is_empty());
+        assert!(VecDeque::from(b).is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter() {
+        use ::rayon::prelude::*;
+        use alloc::vec::Vec;
+
+        let b: BVec<u64> = (1..=1_000_000).collect();
+        assert_eq!(b.par_iter().sum::<u64>(), b.iter().sum::<u64>());
+        assert_eq!(b.par_iter().len(), 1_000_000);
+
+        let b: BVec<i32, 4, 16> = (0..10_000).collect();
+        let v: Vec<i32> = b.par_iter().map(|x| x * 2).collect();
+        assert!(v.iter().copied().eq((0..10_000).map(|x| x * 2)));
+        assert!(b
+            .par_iter()
+            .rev()
+            .copied()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .eq((0..10_000).rev()));
+    }
+
+    #[test]
+    fn test_iter_rev() {
+        let b: BVec<i32, 4, 16> = (0..1_000).collect();
+        assert!(b.iter().rev().copied().eq((0..1_000).rev()));
+        let mut iter = b.range(100..200);
+        assert_eq!(iter.next_back(), Some(&199));
+        assert_eq!(iter.next(), Some(&100));
+        assert!(iter.rev().copied().eq((101..199).rev()));
+
+        // Mix both ends with skipping, cloning and folding.
+        let mut iter = b.range(10..990);
+        assert_eq!(iter.next_back(), Some(&989));
+        assert_eq!(iter.advance_back_by(100), Ok(()));
+        assert_eq!(iter.next_back(), Some(&888));
+        assert_eq!(iter.nth(50), Some(&60));
+        let clone = iter.clone();
+        assert_eq!(iter.next_back(), Some(&887));
+        assert!(clone.rev().copied().eq((61..888).rev()));
+        assert_eq!(iter.len(), 826);
+        assert_eq!(iter.clone().copied().sum::<i32>(), (61..887).sum::<i32>());
+        while iter.len() > 0 {
+            assert_eq!(
+                iter.next_back().unwrap() - iter.next().unwrap(),
+                iter.len() as i32 + 1
+            );
+        }
+        assert_eq!(
+            iter.advance_back_by(2),
+            Err(core::num::NonZeroUsize::new(2).unwrap())
+        );
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_lower_upper_bound_mut() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let mut b = BVec::<(i32, i32), 4, 16>::new_with_params();
+        let mut v = Vec::new();
+
+        for i in 0..2_000 {
+            let x = rng.gen_range(0..100);
+            let lower = v.partition_point(|&(y, _)| y < x);
+            let upper = v.partition_point(|&(y, _)| y <= x);
+
+            let c = b.lower_bound_mut(&(x, i32::MIN));
+            assert_eq!(c.index(), lower);
+            assert_eq!(c.get(), v.get(lower));
+            let mut c = b.upper_bound_mut(&(x, i32::MAX));
+            assert_eq!(c.index(), upper);
+            assert_eq!(c.get(), v.get(upper));
+
+            // Equal keys stay in insertion order.
+            c.insert((x, i));
+            v.insert(upper, (x, i));
+        }
+
+        b.assert_invariants();
+        assert!(b.iter().eq(v.iter()));
+        assert!(v.windows(2).all(|w| w[0] <= w[1]));
+
+        let c = b.lower_bound_mut(&(100, 0));
+        assert!(c.is_past_end());
+        let c = b.upper_bound_mut(&(-1, 0));
+        assert_eq!(c.index(), 0);
+        assert!(BVec::<i32>::new().lower_bound_mut(&0).is_past_end());
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        use alloc::vec::Vec;
+        use rand::{seq::SliceRandom, SeedableRng};
+
+        /// Only compares the key, so that the insertion order of equal
+        /// elements can be checked.
+        #[derive(Clone, Copy, Debug)]
+        struct Keyed(i32, usize);
+        impl PartialEq for Keyed {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Keyed {}
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let mut keys: Vec<i32> = (0..3_000).map(|i| i % 500).collect();
+        keys.shuffle(&mut rng);
+        let values: Vec<Keyed> = keys
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| Keyed(x, i))
+            .collect();
+
+        let mut b = BVec::<Keyed, 4, 16>::new_with_params();
+        for &value in &values {
+            let index = b.insert_sorted(value);
+            assert_eq!(b[index].1, value.1);
+            assert_eq!(index + 1, b.partition_point(|&x| x <= value));
+        }
+
+        let mut sorted = values.clone();
+        sorted.sort();
+        b.assert_invariants();
+        assert!(b
+            .iter()
+            .map(|x| (x.0, x.1))
+            .eq(sorted.iter().map(|x| (x.0, x.1))));
+    }
+
+    #[test]
+    fn test_cursor_get_mut() {
+        let mut b: BVec<i32, 4, 16> = (0..1_000).collect();
+        let mut c = b.cursor_at_mut(0);
+        for offset in [0, 1, 7, 100, 250, -300, 3, -50] {
+            c.move_(offset);
+            let index = c.index() as i32;
+            let value = c.get_mut().unwrap();
+            assert_eq!(*value, index);
+            *value = -index;
+            // A second reference is only created after the first is dead.
+            *c.get_mut().unwrap() -= 1;
+            assert_eq!(c.get(), Some(&(-index - 1)));
+        }
+        c.move_(1_000 - c.index() as isize);
+        assert_eq!(c.get_mut(), None);
+
+        for i in [0, 1, 8, 108, 358, 58, 61, 11] {
+            assert_eq!(b[i], -(i as i32) - 1);
+        }
+        assert_eq!(b.iter().filter(|&&x| x < 0).count(), 8);
+    }
+
+    #[test]
+    fn test_append_from_iter() {
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.append_from_iter(0..0);
+        assert!(b.is_empty());
+        b.append_from_iter(0..10);
+        b.append_from_iter(10..1_000);
+        b.append_from_iter(1_000..1_001);
+        b.append_from_iter(0..0);
+        b.assert_invariants();
+        assert!(b.iter().copied().eq(0..1_001));
+    }
+
+    #[test]
+    fn test_extract_if() {
+        use alloc::vec::Vec;
+
+        let mut b: BVec<i32, 4, 16> = (0..1_000).collect();
+        let evens: Vec<i32> = b.extract_if(|x| *x % 2 == 0).collect();
+        b.assert_invariants();
+        assert!(evens.into_iter().eq((0..1_000).step_by(2)));
+        assert!(b.iter().copied().eq((1..1_000).step_by(2)));
+
+        // Dropping the iterator early still removes all matching elements.
+        let mut extract = b.extract_if(|x| *x % 3 == 0);
+        assert_eq!(extract.next(), Some(3));
+        assert_eq!(extract.next(), Some(9));
+        drop(extract);
+        b.assert_invariants();
+        assert!(b
+            .iter()
+            .copied()
+            .eq((1..1_000).step_by(2).filter(|x| x % 3 != 0)));
+
+        // The predicate can modify the kept elements.
+        assert_eq!(
+            b.extract_if(|x| {
+                *x += 1;
+                false
+            })
+            .count(),
+            0
+        );
+        assert!(b
+            .iter()
+            .copied()
+            .eq((2..=1_000).step_by(2).filter(|x| (x - 1) % 3 != 0)));
+    }
+
+    #[test]
+    fn test_subrange() {
+        let mut b: BVec<i32, 4, 16> = (0..1_000).collect();
+        let mut sub = b.subrange(100..200);
+        sub.assert_invariants();
+        assert!(sub.iter().copied().eq(100..200));
+
+        sub[0] = -1;
+        b[150] = -2;
+        assert_eq!(b[100], 100);
+        assert_eq!(sub[50], 150);
+
+        assert!(b.subrange(..).iter().eq(b.iter()));
+        assert!(b.subrange(1_000..).is_empty());
+        assert!(b.subrange(..=0).iter().copied().eq([0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "range end 1001 out of range for length 1000")]
+    fn test_subrange_out_of_bounds() {
+        let b: BVec<i32> = (0..1_000).collect();
+        let _ = b.subrange(990..1_001);
+    }
+
+    #[test]
+    fn test_into_iter_frees_nodes() {
+        let b: BVec<i32, 4, 16> = (0..100_000).collect();
+        let initial_nodes = b.node_count();
+
+        let mut iter = b.into_iter();
+        for i in 0..100_000 {
+            assert_eq!(iter.next(), Some(i));
+            let remaining = iter.as_bvec();
+            if i % 10_000 == 9_999 {
+                remaining.assert_invariants();
+                // The nodes stay at least half full, so every node holds
+                // on average at least one remaining element.
+                assert!(remaining.node_count() <= remaining.len().max(1));
+                assert!(remaining.node_count() < initial_nodes);
+            }
+        }
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.as_bvec().node_count(), 0);
+    }
+
+    #[test]
+    fn test_sort() {
+        use alloc::vec::Vec;
+        use rand::{seq::SliceRandom, SeedableRng};
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let mut v: Vec<(i32, i32)> = (0..1_000).map(|i| (i % 100, i)).collect();
+        v.shuffle(&mut rng);
+        let mut b: BVec<_, 4, 16> = v.iter().copied().collect();
+
+        // Only sorting by the first field checks that the sort is stable.
+        b.sort_by(|x, y| x.0.cmp(&y.0));
+        v.sort_by_key(|x| x.0);
+        b.assert_invariants();
+        assert!(b.iter().eq(v.iter()));
+
+        v.shuffle(&mut rng);
+        b = v.iter().copied().collect();
+        b.sort();
+        v.sort();
+        assert!(b.iter().eq(v.iter()));
+
+        b.sort_unstable_by(|x, y| y.cmp(x));
+        assert!(b.iter().eq(v.iter().rev()));
+        b.sort_unstable();
+        assert!(b.iter().eq(v.iter()));
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            b.sort_by(|_, _| panic!("boom"));
+        }));
+        assert!(result.is_err());
+        b.assert_invariants();
+        assert_eq!(b.len(), 1_000);
+
+        let mut empty = BVec::<i32>::new();
+        empty.sort();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_insert_sorted_by_key() {
+        use alloc::vec::Vec;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let mut b = BVec::<(i32, usize), 4, 16>::new_with_params();
+        let mut by_desc = BVec::<(i32, usize), 4, 16>::new_with_params();
+        let mut v = Vec::new();
+
+        for i in 0..2_000 {
+            let value = (rng.gen_range(0..50), i);
+            let index = b.insert_sorted_by_key(value, |&(key, _)| key);
+            assert_eq!(b[index], value);
+            // Equal keys are inserted after the existing ones.
+            assert_eq!(index + 1, b.partition_point(|&(key, _)| key <= value.0));
+
+            let index = by_desc.insert_sorted_by(value, |x, y| y.0.cmp(&x.0));
+            assert_eq!(by_desc[index], value);
+            v.push(value);
+        }
+
+        v.sort_by_key(|&(key, _)| key);
+        assert!(b.iter().eq(v.iter()));
+        v.sort_by_key(|&(key, _)| -key);
+        assert!(by_desc.iter().eq(v.iter()));
+    }
+
+    #[test]
+    fn test_cursor_pair() {
+        use alloc::vec::Vec;
+
+        // Two sorted halves, merged by moving one cursor in each.
+        let b: BVec<i32, 4, 16> = (0..500)
+            .map(|x| 2 * x)
+            .chain((0..500).map(|x| 3 * x))
+            .collect();
+        let (mut left, mut right) = b.cursor_pair(0, 500);
+        let mut merged = Vec::new();
+        while left.index() < 500 || right.is_inbounds() {
+            let take_left = match (left.get().filter(|_| left.index() < 500), right.get()) {
+                (Some(x), Some(y)) => x <= y,
+                (l, _) => l.is_some(),
+            };
+            if take_left {
+                merged.push(*left.get().unwrap());
+                left.move_next();
+            } else {
+                merged.push(*right.get().unwrap());
+                right.move_next();
+            }
+        }
+
+        let mut expected: Vec<i32> = b.iter().copied().collect();
+        expected.sort();
+        assert_eq!(merged, expected);
+        assert_eq!((left.index(), right.index()), (500, 1_000));
+    }
+
+    #[test]
+    fn test_find_byte() {
+        let mut b: BVec<u8> = core::iter::repeat_n(0, 1_000_000).collect();
+        assert_eq!(b.find_byte(1), None);
+        assert_eq!(b.find_byte(0), Some(0));
+
+        b[999_990] = 1;
+        b[999_995] = 1;
+        b[999_999] = 2;
+        assert_eq!(b.find_byte(1), Some(999_990));
+        assert_eq!(b.find_byte(2), Some(999_999));
+
+        let b: BVec<u8, 4, 16> = (0..=255).cycle().skip(1).take(1_000).collect();
+        assert_eq!(b.find_byte(0), Some(255));
+        assert_eq!(b.find_byte(17), Some(16));
+        assert_eq!(BVec::<u8>::new().find_byte(0), None);
+    }
+
+    #[test]
+    fn test_cursor_seek_to() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        bvec.extend(0..1000);
+
+        let mut cursor = bvec.cursor_at(500);
+        for index in [503, 497, 520, 3, 999, 1000, 0, 998, 501, 500] {
+            cursor.seek_to(index);
+            assert_eq!(cursor.index(), index);
+            assert_eq!(cursor.get(), bvec.get(index));
+        }
+
+        let mut cursor = bvec.cursor_at_mut(0);
+        for index in [1, 0, 4, 3, 16, 15, 1000, 999, 250] {
+            cursor.seek_to(index);
+            assert_eq!(cursor.index(), index);
+            assert_eq!(
+                cursor.get().copied(),
+                (index < 1000).then_some(index as i32)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cursor_seek_to_out_of_bounds() {
+        let bvec: BVec<i32> = (0..10).collect();
+        bvec.cursor_at(0).seek_to(11);
+    }
+
+    #[test]
+    fn test_count() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        assert_eq!(bvec.count(&0), 0);
+        bvec.extend((0..1000).map(|x| x % 7));
+        for x in 0..7 {
+            let expected = (0..1000).filter(|y| y % 7 == x).count();
+            assert_eq!(bvec.count(&x), expected);
+        }
+        assert_eq!(bvec.count(&7), 0);
+    }
+
+    #[test]
+    fn test_truncate_front() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        bvec.extend(0..1000);
+        bvec.truncate_front(1000);
+        assert_eq!(bvec.len(), 1000);
+        bvec.truncate_front(100);
+        assert!(bvec.iter().copied().eq(900..1000));
+        bvec.assert_invariants();
+        bvec.truncate_front(0);
+        assert!(bvec.is_empty());
     }
 
     #[test]
-    fn test_random_removals() {
-        use alloc::vec::Vec;
-        use rand::{Rng, SeedableRng};
+    fn test_into_iter_double_ended() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        bvec.extend(0..1000);
+        let mut iter = bvec.into_iter();
+        assert_eq!(iter.len(), 1000);
+        for i in 0..300 {
+            assert_eq!(iter.next(), Some(i));
+            assert_eq!(iter.next_back(), Some(999 - i));
+        }
+        assert_eq!(iter.len(), 400);
+        assert!(iter.as_bvec().iter().copied().eq(300..700));
+        assert!(iter.by_ref().rev().eq((300..700).rev()));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.len(), 0);
+    }
 
-        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+    #[test]
+    fn test_starts_ends_with() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        assert!(bvec.starts_with(&[]));
+        assert!(bvec.ends_with(&[]));
+        assert!(!bvec.starts_with(&[0]));
+        assert!(!bvec.ends_with(&[0]));
 
-        let mut v = Vec::new();
-        let mut b_4_2 = BVec::<i32>::new();
-        let mut b_5_1 = BVec::<i32>::new();
+        bvec.extend(0..100);
+        let all: Vec<i32> = (0..100).collect();
+        assert!(bvec.starts_with(&[]));
+        assert!(bvec.ends_with(&[]));
+        assert!(bvec.starts_with(&all[..37]));
+        assert!(bvec.ends_with(&all[63..]));
+        assert!(bvec.starts_with(&all));
+        assert!(bvec.ends_with(&all));
+        assert!(!bvec.starts_with(&all[1..38]));
+        assert!(!bvec.ends_with(&all[62..99]));
+        assert!(!bvec.starts_with(&[0, 1, 2, 4]));
 
-        for x in 0..1000 {
-            v.push(x);
-            b_4_2.push_back(x);
-            b_5_1.push_back(x);
+        let mut longer = all.clone();
+        longer.push(100);
+        assert!(!bvec.starts_with(&longer));
+        assert!(!bvec.ends_with(&longer));
+    }
+
+    #[test]
+    fn test_concat_join() {
+        let mut outer = BVec::<BVec<i32, 4, 16>, 4, 16>::new_with_params();
+        assert!(outer.clone().concat().is_empty());
+        assert!(outer.clone().join(&-1).is_empty());
+
+        for range in [0..100, 100..100, 100..1000] {
+            let mut inner = BVec::new_with_params();
+            inner.extend(range);
+            outer.push_back(inner);
         }
 
-        while !v.is_empty() {
-            let index = rng.gen_range(0..v.len());
-            let v_rem = v.remove(index);
-            let b_4_2_rem = b_4_2.remove(index);
-            let b_5_1_rem = b_5_1.remove(index);
-            assert_eq!(v.len(), b_4_2.len());
-            assert_eq!(v.len(), b_5_1.len());
-            assert_eq!(v_rem, b_5_1_rem);
-            assert_eq!(v_rem, b_4_2_rem);
+        let concat = outer.clone().concat();
+        assert_eq!(concat.len(), 1000);
+        assert!(concat.iter().copied().eq(0..1000));
+        concat.assert_invariants();
+
+        let join = outer.join(&-1);
+        assert_eq!(join.len(), 1002);
+        assert!(join
+            .iter()
+            .copied()
+            .eq((0..100).chain([-1, -1]).chain(100..1000)));
+        join.assert_invariants();
+    }
+
+    #[test]
+    fn test_contiguous_run() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        assert_eq!(bvec.contiguous_run(0), None);
+        bvec.extend(0..1000);
+        assert_eq!(bvec.contiguous_run(1000), None);
+
+        let mut leaf_ends = Vec::new();
+        for leaf in LeafSlices::new(&bvec) {
+            leaf_ends.push(leaf_ends.last().unwrap_or(&0) + leaf.len());
+        }
+        let mut leaf_ends = leaf_ends.into_iter().peekable();
+        for index in 0..1000 {
+            if leaf_ends.peek() == Some(&index) {
+                leaf_ends.next();
+            }
+            let run = bvec.contiguous_run(index).unwrap();
+            assert_eq!(index + run.len(), *leaf_ends.peek().unwrap());
+            assert!(run
+                .iter()
+                .copied()
+                .eq(index as i32..(index + run.len()) as i32));
         }
 
-        assert!(b_4_2.is_empty());
-        assert!(b_5_1.is_empty());
+        let mut index = 0;
+        while let Some(run) = bvec.contiguous_run(index) {
+            assert!(!run.is_empty() && run.len() <= 4);
+            index += run.len();
+        }
+        assert_eq!(index, 1000);
     }
 
     #[test]
-    fn test_random_double_insertions() {
-        use alloc::vec::Vec;
-        use rand::{Rng, SeedableRng};
+    fn test_take() {
+        use alloc::boxed::Box;
 
-        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let mut bvec = BVec::<Box<i32>, 4, 16>::new_with_params();
+        assert!(bvec.take().is_empty());
+        bvec.extend((0..1000).map(Box::new));
+        let taken = bvec.take();
+        assert!(bvec.is_empty());
+        assert_eq!(bvec.height(), 0);
+        assert_eq!(bvec.iter().next(), None);
+        assert!(taken.iter().map(|x| **x).eq(0..1000));
+        taken.assert_invariants();
 
-        let mut v = Vec::new();
-        let mut b_7_3 = BVec::<i32>::new();
-        let mut b_5_5 = BVec::<i32>::new();
+        bvec.push_back(Box::new(-1));
+        assert_eq!(bvec.len(), 1);
+        assert_eq!(*bvec[0], -1);
+    }
 
-        for x in 0..500 {
-            let index = rng.gen_range(0..=v.len());
-            v.insert(index, 2 * x);
-            v.insert(index, 2 * x + 1);
-            let mut cursor_7_3 = b_7_3.cursor_at_mut(index);
-            let mut cursor_5_5 = b_5_5.cursor_at_mut(index);
-            cursor_7_3.insert(2 * x);
-            cursor_7_3.insert(2 * x + 1);
-            cursor_5_5.insert(2 * x);
-            cursor_5_5.insert(2 * x + 1);
-            assert_eq!(v.len(), b_7_3.len());
-            assert_eq!(v.len(), b_5_5.len());
+    #[test]
+    fn test_insert_if_absent() {
+        use alloc::collections::BTreeSet;
+
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        let mut set = BTreeSet::new();
+        for i in 0..2000 {
+            let x = (i * 7919) % 613;
+            assert_eq!(bvec.insert_if_absent(x), set.insert(x));
         }
+        assert_eq!(bvec.len(), set.len());
+        assert!(bvec.iter().eq(set.iter()));
+        bvec.assert_invariants();
+    }
 
-        assert_eq!(v, b_7_3.iter().copied().collect::<Vec<_>>());
-        assert_eq!(v, b_5_5.iter().copied().collect::<Vec<_>>());
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        let mut next = 0;
+        let mut f = || {
+            next += 1;
+            -next
+        };
+
+        *bvec.get_or_insert_with(2, &mut f) += 100;
+        assert!(bvec.iter().copied().eq([-1, -2, 97]));
+
+        assert_eq!(*bvec.get_or_insert_with(1, &mut f), -2);
+        assert_eq!(bvec.len(), 3);
+
+        assert_eq!(*bvec.get_or_insert_with(999, &mut f), -1000);
+        assert_eq!(bvec.len(), 1000);
+        assert!(bvec.iter().skip(3).copied().eq((4..=1000).map(|x| -x)));
+        bvec.assert_invariants();
     }
 
     #[test]
-    #[should_panic]
-    fn test_remove_past_end() {
-        let mut v = BVec::<i32>::new();
-        for x in 0..10 {
-            v.push_back(x);
+    fn test_reverse_range() {
+        use alloc::vec::Vec;
+
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        bvec.extend(0..1000);
+        let mut vec: Vec<i32> = (0..1000).collect();
+
+        for range in [100..200, 0..1000, 5..5, 5..6, 0..3, 997..1000, 1..998] {
+            bvec.reverse_range(range.clone());
+            vec[range].reverse();
+            assert!(bvec.iter().eq(vec.iter()));
         }
-        let mut c = v.cursor_at_mut(10);
-        c.remove();
+        bvec.reverse_range(..);
+        vec.reverse();
+        assert!(bvec.iter().eq(vec.iter()));
+        bvec.assert_invariants();
     }
 
     #[test]
     #[should_panic]
-    fn test_remove_past_end_root() {
-        let mut v = BVec::<i32>::new();
-        for x in 0..10 {
-            v.push_back(x);
-        }
-        let mut c = v.cursor_at_mut(10);
-        c.remove();
+    fn test_reverse_range_out_of_bounds() {
+        let mut bvec: BVec<i32> = (0..10).collect();
+        bvec.reverse_range(5..11);
     }
 
     #[test]
-    fn test_random_removals2() {
+    fn test_rotate_range() {
         use alloc::vec::Vec;
-        use rand::{Rng, SeedableRng};
 
-        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
-
-        let mut v = Vec::new();
-        let mut b_4_4 = BVec::<i32>::new();
-        let mut b_5_5 = BVec::<i32>::new();
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        bvec.extend(0..1000);
+        let mut vec: Vec<i32> = (0..1000).collect();
 
-        for x in 0..1000 {
-            v.push(x);
-            b_4_4.push_back(x);
-            b_5_5.push_back(x);
+        for (range, mid) in [
+            (100..200, 30),
+            (0..1000, 999),
+            (5..5, 0),
+            (10..20, 0),
+            (10..20, 10),
+            (997..1000, 1),
+            (1..998, 500),
+        ] {
+            bvec.rotate_range(range.clone(), mid);
+            vec[range].rotate_left(mid);
+            assert!(bvec.iter().eq(vec.iter()));
         }
+        bvec.assert_invariants();
+    }
 
-        while !v.is_empty() {
-            let index = rng.gen_range(0..v.len());
-            let v_rem = v.remove(index);
-            let b_4_4_rem = b_4_4.remove(index);
-            let b_5_5_rem = b_5_5.remove(index);
-            assert_eq!(v.len(), b_4_4.len());
-            assert_eq!(v.len(), b_5_5.len());
-            assert_eq!(v_rem, b_5_5_rem);
-            assert_eq!(v_rem, b_4_4_rem);
-        }
-        assert!(b_4_4.is_empty());
-        assert!(b_5_5.is_empty());
+    #[test]
+    #[should_panic]
+    fn test_rotate_range_mid_out_of_bounds() {
+        let mut bvec: BVec<i32> = (0..10).collect();
+        bvec.rotate_range(2..5, 4);
     }
 
     #[test]
-    fn test_bvec_debug() {
-        use alloc::format;
-        use alloc::vec::Vec;
+    fn test_iter_early_exit() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        bvec.extend(0..1000);
 
-        let v = Vec::from_iter(0..1000);
-        let mut b = BVec::new();
-        for x in 0..1000 {
-            b.push_back(x);
-        }
+        let mut iter = bvec.range(10..990);
+        assert_eq!(iter.find(|&&x| x % 100 == 37), Some(&37));
+        assert_eq!(iter.len(), 952);
+        assert_eq!(iter.next(), Some(&38));
+        assert_eq!(iter.position(|&x| x == 200), Some(161));
+        assert_eq!(iter.next(), Some(&201));
+        assert_eq!(iter.find_map(|&x| (x > 500).then_some(x * 2)), Some(1002));
+        assert!(iter.any(|&x| x == 600));
+        assert_eq!(iter.next(), Some(&601));
+        assert!(!iter.all(|&x| x < 700));
+        assert_eq!(iter.next(), Some(&701));
+        assert!(iter.all(|&x| x < 990));
+        assert_eq!(iter.next(), None);
 
-        assert_eq!(format!("{v:?}"), format!("{b:?}"));
+        let mut iter = bvec.range(10..990);
+        assert_eq!(iter.find(|&&x| x >= 990), None);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(bvec.iter().position(|&x| x == 999), Some(999));
+        assert!(!bvec.range(..0).any(|_| true));
+        assert!(bvec.range(500..500).all(|_| false));
     }
 
     #[test]
-    fn test_random_double_removals() {
+    fn test_iter_fold() {
         use alloc::vec::Vec;
-        use rand::{Rng, SeedableRng};
 
-        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        let hash = |acc: i32, &x: &i32| acc.wrapping_mul(31).wrapping_add(x);
+        assert_eq!(bvec.iter().fold(7, hash), 7);
+        bvec.extend(0..1000);
 
-        let mut v = Vec::new();
-        let mut b_4_4 = BVec::<i32>::new();
-        let mut b_5_5 = BVec::<i32>::new();
+        let expected = (0..1000).collect::<Vec<_>>().iter().fold(7, hash);
+        assert_eq!(bvec.iter().fold(7, hash), expected);
+        assert_eq!(bvec.iter().sum::<i32>(), 499_500);
+        assert_eq!(bvec.range(3..=3).fold(7, hash), 7 * 31 + 3);
 
-        for x in 0..1000 {
-            v.push(x);
-            b_4_4.push_back(x);
-            b_5_5.push_back(x);
-        }
+        let mut visited = Vec::new();
+        bvec.range(10..990).for_each(|&x| visited.push(x));
+        assert!(visited.into_iter().eq(10..990));
 
-        while !v.is_empty() {
-            let index = rng.gen_range(0..v.len() - 1);
-            let v1 = v.remove(index);
-            let v2 = v.remove(index);
-            {
-                let mut cursor_5_5 = b_5_5.cursor_at_mut(index);
-                let b1 = cursor_5_5.remove();
-                let b2 = cursor_5_5.remove();
-                assert_eq!(b1, v1);
-                assert_eq!(b2, v2);
-                assert_eq!(v.len(), b_5_5.len());
-            }
-            {
-                let mut cursor_4_4 = b_4_4.cursor_at_mut(index);
-                let b1 = cursor_4_4.remove();
-                let b2 = cursor_4_4.remove();
-                assert_eq!(b1, v1);
-                assert_eq!(b2, v2);
-                assert_eq!(v.len(), b_4_4.len());
-            }
-        }
+        let mut iter = bvec.iter();
+        iter.nth(499);
+        assert!(iter
+            .fold(Vec::new(), |mut acc, &x| {
+                acc.push(x);
+                acc
+            })
+            .into_iter()
+            .eq(500..1000));
     }
 
     #[test]
-    fn test_random_cursor_move_right() {
-        use rand::{Rng, SeedableRng};
+    fn test_try_get() {
+        use alloc::string::ToString;
 
-        let mut rng = rand::rngs::StdRng::from_seed([123; 32]);
-        let mut b = BVec::<i32>::new();
-        let n = 1000;
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        assert_eq!(bvec.try_get(0), Err(IndexError { index: 0, len: 0 }));
 
-        for x in 0..n as i32 {
-            b.push_back(x);
-        }
+        bvec.extend(0..100);
+        assert_eq!(bvec.try_get(0), Ok(&0));
+        assert_eq!(bvec.try_get(99), Ok(&99));
+        *bvec.try_get_mut(50).unwrap() = -1;
+        assert_eq!(bvec[50], -1);
 
-        for _ in 0..n {
-            let (start, end) = (rng.gen_range(0..b.len()), rng.gen_range(0..b.len()));
+        let err = bvec.try_get(100).unwrap_err();
+        assert_eq!(
+            err,
+            IndexError {
+                index: 100,
+                len: 100
+            }
+        );
+        assert_eq!(
+            bvec.try_get_mut(usize::MAX),
+            Err(IndexError {
+                index: usize::MAX,
+                len: 100
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "index out of bounds: the len is 100 but the index is 100"
+        );
+    }
+    #[test]
+    fn test_cursor_at_checked() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        assert!(bvec.cursor_at_checked(0).unwrap().is_past_end());
+        assert!(bvec.cursor_at_checked(1).is_none());
 
-            let mut c = b.cursor_at_mut(start);
-            c.move_(end as isize - start as isize);
-            assert_eq!(Some(&(end as i32)), c.get());
+        bvec.extend(0..100);
+        for index in [0, 1, 37, 99, 100] {
+            let stored = bvec.cursor_at(index).index();
+            let cursor = bvec.cursor_at_checked(stored).unwrap();
+            assert_eq!(cursor.index(), index);
+            assert_eq!(cursor.get(), bvec.get(index));
         }
+        assert!(bvec.cursor_at_checked(101).is_none());
+        assert!(bvec.cursor_at_checked(usize::MAX).is_none());
     }
 
     #[test]
-    fn test_random_cursor_get() {
-        let mut b_4_4 = BVec::<i32>::new();
-        let mut b_5_5 = BVec::<i32>::new();
-        let n = 1000;
+    fn test_min_max() {
+        use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
 
-        for x in 0..n as i32 {
-            b_4_4.push_back(x);
-            b_5_5.push_back(x);
-        }
+        let mut bvec = BVec::<(i32, usize), 4, 16>::new_with_params();
+        assert_eq!(bvec.min_element(), None);
+        assert_eq!(bvec.max_element(), None);
+        assert_eq!(bvec.min_by_key(|x| x.0), None);
+        assert_eq!(bvec.max_by_key(|x| x.0), None);
 
-        for i in 0..n {
-            let x = *b_4_4.get(i).unwrap();
-            let y = *b_5_5.get(i).unwrap();
+        bvec.push_back((5, 0));
+        assert_eq!(bvec.min_element(), Some(&(5, 0)));
+        assert_eq!(bvec.max_element(), Some(&(5, 0)));
+        assert_eq!(bvec.min_by_key(|x| x.0), Some(&(5, 0)));
+        assert_eq!(bvec.max_by_key(|x| x.0), Some(&(5, 0)));
 
-            assert_eq!(x, y);
-            assert_eq!(x, *b_4_4.cursor_at_mut(i).get().unwrap());
-            assert_eq!(y, *b_5_5.cursor_at_mut(i).get().unwrap());
-        }
+        let mut values: Vec<i32> = (0..1000).map(|x| x / 2).collect();
+        values.shuffle(&mut SmallRng::seed_from_u64(0));
+        bvec.clear();
+        bvec.extend(values.iter().copied().zip(0..));
+        let first = |x| values.iter().position(|&y| y == x).unwrap();
+        let last = |x| values.iter().rposition(|&y| y == x).unwrap();
+
+        assert_eq!(bvec.min_element(), Some(&(0, first(0))));
+        assert_eq!(bvec.max_element(), Some(&(499, last(499))));
+        assert_eq!(bvec.min_by_key(|x| x.0), Some(&(0, first(0))));
+        assert_eq!(bvec.max_by_key(|x| x.0), Some(&(499, last(499))));
+        assert_eq!(bvec.min_by_key(|x| -x.0), Some(&(499, first(499))));
     }
 
     #[test]
-    fn test_bvec_iter() {
-        let n = 1000;
-        let mut b = BVec::new();
-        for x in 0..n {
-            b.push_back(x);
-        }
+    fn test_sum_product() {
+        let mut bvec = BVec::<i64, 4, 16>::new_with_params();
+        assert_eq!(bvec.sum::<i64>(), 0);
+        assert_eq!(bvec.product::<i64>(), 1);
 
-        assert!(b.iter().copied().eq(0..n));
+        bvec.extend(0..1000);
+        assert_eq!(bvec.sum::<i64>(), 499_500);
+        assert_eq!(bvec.product::<i64>(), 0);
+
+        bvec.clear();
+        bvec.extend(1..=20);
+        assert_eq!(bvec.product::<i64>(), 2_432_902_008_176_640_000);
     }
 
     #[test]
-    fn test_bvec_extend() {
-        let n = 500;
-        let mut b = BVec::new();
-        for x in 0..n {
-            b.push_back(x);
+    fn test_copy_within() {
+        use alloc::vec::Vec;
+
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        bvec.extend(0..1000);
+        let mut vec: Vec<i32> = (0..1000).collect();
+
+        for (src, dest) in [
+            (100..200, 150),
+            (100..200, 50),
+            (300..400, 300),
+            (0..1000, 0),
+            (0..500, 500),
+            (500..1000, 0),
+            (10..10, 1000),
+            (990..1000, 995 - 10),
+            (3..7, 4),
+            (4..8, 3),
+        ] {
+            bvec.copy_within(src.clone(), dest);
+            vec.copy_within(src, dest);
+            assert!(bvec.iter().eq(vec.iter()));
         }
+        bvec.assert_invariants();
+    }
 
-        b.extend(n..2 * n);
+    #[test]
+    #[should_panic]
+    fn test_copy_within_dest_out_of_bounds() {
+        let mut bvec: BVec<i32> = (0..10).collect();
+        bvec.copy_within(2..5, 8);
+    }
 
-        assert!(b.iter().copied().eq(0..2 * n));
+    #[test]
+    fn test_write_from() {
+        let mut bvec = BVec::<i32, 4, 16>::new_with_params();
+        assert_eq!(bvec.write_from(0, 0..10), 0);
+        assert!(bvec.is_empty());
+
+        bvec.extend(0..1000);
+        assert_eq!(bvec.write_from(100, (0..50).map(|x| -x)), 50);
+        assert!(bvec.range(..100).copied().eq(0..100));
+        assert!(bvec.range(100..150).copied().eq((0..50).map(|x| -x)));
+        assert!(bvec.range(150..).copied().eq(150..1000));
+
+        let mut values = 0..100;
+        assert_eq!(bvec.write_from(990, &mut values), 10);
+        assert_eq!(values.next(), Some(10));
+        assert!(bvec.range(990..).copied().eq(0..10));
+        assert_eq!(bvec.len(), 1000);
+        assert_eq!(bvec.write_from(1000, 0..10), 0);
     }
 
     #[test]
-    fn test_bvec_move_empty_cursor() {
-        let mut b = BVec::<i32>::new();
-        let mut c = b.cursor_at_mut(0);
-        c.move_(0);
+    #[should_panic]
+    fn test_write_from_out_of_bounds() {
+        let mut bvec: BVec<i32> = (0..10).collect();
+        bvec.write_from(11, 0..1);
     }
 
     #[test]
-    fn test_bvec_hash() {
-        let n = 1000;
-        let mut b = BVec::new();
-        b.extend(0..n);
+    fn test_extract_if_panicking_pred() {
+        use alloc::{rc::Rc, vec::Vec};
+        use std::panic::{catch_unwind, AssertUnwindSafe};
 
-        let v = alloc::vec::Vec::from_iter(0..n);
+        let rc = Rc::new(());
+        let mut b = BVec::<(i32, Rc<()>), 4, 16>::new_with_params();
+        b.extend((0..1_000).map(|x| (x, Rc::clone(&rc))));
 
-        let mut v_hasher = std::collections::hash_map::DefaultHasher::new();
-        v.hash(&mut v_hasher);
-        let v_hash = v_hasher.finish();
+        let mut extracted = Vec::new();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            extracted.extend(b.extract_if(|x| {
+                assert!(x.0 != 501, "boom");
+                x.0 % 2 == 1
+            }));
+        }));
+        assert!(result.is_err());
+        b.assert_invariants();
+        assert!(extracted.iter().map(|x| x.0).eq((1..501).step_by(2)));
+        assert!(b
+            .iter()
+            .map(|x| x.0)
+            .eq((0..500).step_by(2).chain(500..1_000)));
+        assert_eq!(Rc::strong_count(&rc), 1 + b.len() + extracted.len());
 
-        let mut b_hasher = std::collections::hash_map::DefaultHasher::new();
-        b.hash(&mut b_hasher);
-        let b_hash = b_hasher.finish();
+        drop(extracted);
+        drop(b);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
 
-        assert_eq!(v_hash, b_hash);
+    #[test]
+    fn test_send_sync() {
+        use std::thread;
+
+        let mut b = BVec::<i32, 4, 16>::new_with_params();
+        b.extend(0..1_000);
+        let b = thread::spawn(move || {
+            b.assert_invariants();
+            b
+        })
+        .join()
+        .unwrap();
+
+        let sums: [i32; 2] = thread::scope(|s| {
+            let front = s.spawn(|| b.range(..500).sum());
+            let back = s.spawn(|| b.range(500..).sum());
+            [front.join().unwrap(), back.join().unwrap()]
+        });
+        assert_eq!(sums, [(0..500).sum::<i32>(), (500..1_000).sum::<i32>()]);
     }
 
     #[test]
@@ -591,11 +4666,36 @@ mod tests {
         assert!(cursor.get().is_none());
     }
 
-    // #[test]
-    // #[should_panic(expected = "length overflow")]
-    // fn test_zst_length_overflow() {
-    //     let mut b = BTreeVec::<i32, 10, { usize::MAX / 3 }>::new();
-    // }
+    #[test]
+    fn test_zst_length_overflow() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut b = BVec::<()>::new();
+        b.push_back(());
+        // A single leaf cannot hold this many values, but a root leaf does
+        // not store its length anywhere else than in the tree.
+        b.len = isize::MAX as usize - 1;
+        assert_eq!(b.try_insert(0, ()), Ok(()));
+        assert_eq!(b.len(), isize::MAX as usize);
+        assert_eq!(b.try_insert(0, ()), Err(()));
+        assert_eq!(b.try_insert(b.len(), ()), Err(()));
+
+        let res = catch_unwind(AssertUnwindSafe(|| b.push_back(())));
+        assert!(res.is_err());
+        assert_eq!(b.len(), isize::MAX as usize);
+
+        b.len = 2;
+        assert_eq!(b.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_try_insert() {
+        let mut b: BVec<i32> = (0..10).collect();
+        assert_eq!(b.try_insert(11, 5), Err(5));
+        assert_eq!(b.try_insert(10, 10), Ok(()));
+        assert_eq!(b.try_insert(0, -1), Ok(()));
+        assert!(b.iter().copied().eq(-1..=10));
+    }
 
     #[test]
     #[cfg_attr(miri, ignore)]
@@ -604,6 +4704,63 @@ mod tests {
         t.compile_fail("tests/compile_fail/test_cursormut_invariant.rs");
     }
 
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary() {
+        use ::arbitrary::{Arbitrary, Unstructured};
+
+        let bytes: Vec<u8> = (1..=255).step_by(2).cycle().take(4096).collect();
+        let b = BVec::<u16>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        let v = Vec::<u16>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        assert!(!b.is_empty());
+        assert!(b.iter().eq(&v));
+
+        let b = BVec::<u16>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        let v = Vec::<u16>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert!(b.iter().eq(&v));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_write() {
+        use std::io::Write;
+
+        let mut v = Vec::new();
+        let mut b = BVec::<u8>::new();
+        for i in 0..200 {
+            writeln!(v, "line {i}: {:?}", (i, "abc")).unwrap();
+            writeln!(b, "line {i}: {:?}", (i, "abc")).unwrap();
+        }
+        b.write_all(&[0; 1000]).unwrap();
+        v.write_all(&[0; 1000]).unwrap();
+        b.flush().unwrap();
+        assert!(b.iter().eq(&v));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let b: BVec<i32> = (0..1000).collect();
+        let json = serde_json::to_string(&b).unwrap();
+        assert_eq!(json, serde_json::to_string(&b.to_vec()).unwrap());
+        let b2: BVec<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(b, b2);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_invalid_branch_factor() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/compile_fail/test_invalid_branch_factor.rs");
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_itermut_invariant() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/compile_fail/test_itermut_invariant.rs");
+    }
+
     #[test]
     fn test_bvec_covariant() {
         fn foo<'a>(_x: BVec<&'a i32>, _y: &'a i32) {}
@@ -1,5 +1,10 @@
 mod fenwick;
+pub mod free;
 pub mod handle;
+pub mod join;
+pub mod reverse;
+pub mod search;
+pub mod split;
 
 use core::{
     alloc::Layout,
@@ -15,24 +20,29 @@ use alloc::{
 
 use self::{fenwick::FenwickTree, handle::InternalMut};
 
-/// SAFETY: BRANCH_FACTOR must be less than u8::MAX.
-#[cfg(miri)]
-const BRANCH_FACTOR: usize = 4;
-#[cfg(not(miri))]
-const BRANCH_FACTOR: usize = 32;
-
-/// SAFETY: LEAF_CAP_BYTES must be less than u16::MAX.
-#[cfg(miri)]
-const LEAF_CAP_BYTES: usize = 16;
-#[cfg(not(miri))]
-const LEAF_CAP_BYTES: usize = 256;
+/// Checks the node size parameters of a tree, see [`BVec`](crate::BVec).
+pub struct AssertParams<const B: usize, const C: usize>;
+
+impl<const B: usize, const C: usize> AssertParams<B, C> {
+    /// SAFETY: `B` must be less than `u8::MAX` and `C` must be less than `u16::MAX`.
+    pub const OK: () = {
+        assert!(
+            B.is_power_of_two() && 4 <= B && B <= 128,
+            "the branch factor must be a power of two between 4 and 128"
+        );
+        assert!(
+            C < u16::MAX as usize,
+            "the leaf size must be less than 65535 bytes"
+        );
+    };
+}
 
-pub struct RawNodeWithLen<T>(pub usize, pub NodePtr<T>);
+pub struct RawNodeWithLen<T, const B: usize, const C: usize>(pub usize, pub NodePtr<T, B, C>);
 
-pub type NodePtr<T> = NonNull<NodeBase<T>>;
+pub type NodePtr<T, const B: usize, const C: usize> = NonNull<NodeBase<T, B, C>>;
 
-pub struct NodeBase<T> {
-    pub parent: Option<NodePtr<T>>,
+pub struct NodeBase<T, const B: usize, const C: usize> {
+    pub parent: Option<NodePtr<T, B, C>>,
     pub parent_index: MaybeUninit<u8>,
     height: u8,
     children_len: u16,
@@ -40,19 +50,19 @@ pub struct NodeBase<T> {
 }
 
 #[repr(C)]
-pub struct InternalNode<T> {
-    base: NodeBase<T>,
-    lengths: FenwickTree,
-    pub children: [MaybeUninit<NodePtr<T>>; BRANCH_FACTOR],
+pub struct InternalNode<T, const B: usize, const C: usize> {
+    base: NodeBase<T, B, C>,
+    lengths: FenwickTree<B>,
+    pub children: [MaybeUninit<NodePtr<T, B, C>>; B],
 }
 
 // #[repr(C)]
 // pub struct LeafNode<T, > {
-//     base: NodeBase<T>,
+//     base: NodeBase<T, B, C>,
 //     values: [MaybeUninit<T>; C],
 // }
 
-impl<T> NodeBase<T> {
+impl<T, const B: usize, const C: usize> NodeBase<T, B, C> {
     pub const fn new(height: u8) -> Self {
         Self {
             parent: None,
@@ -68,21 +78,22 @@ impl<T> NodeBase<T> {
     }
 }
 
-impl<T> NodeBase<T> {
-    const LEAF_CAP: usize = if size_of::<T>() <= LEAF_CAP_BYTES {
+impl<T, const B: usize, const C: usize> NodeBase<T, B, C> {
+    pub const LEAF_CAP: usize = if size_of::<T>() <= C {
         if size_of::<T>() == 0 {
-            // TODO: should this be 0?
-            1
+            // Values of a zero-sized type take no space in the leaf, so
+            // only the `u16` length limits how many of them fit.
+            u16::MAX as usize
         } else {
-            LEAF_CAP_BYTES / size_of::<T>()
+            C / size_of::<T>()
         }
     } else {
         1
     };
 
-    pub fn new_leaf() -> NodePtr<T> {
+    pub fn new_leaf() -> NodePtr<T, B, C> {
         let (layout, _) = Self::leaf_layout();
-        let ptr = unsafe { alloc(layout).cast::<NodeBase<T>>() };
+        let ptr = unsafe { alloc(layout).cast::<NodeBase<T, B, C>>() };
         let Some(node_ptr) = NonNull::new(ptr) else {
             handle_alloc_error(layout);
         };
@@ -91,7 +102,7 @@ impl<T> NodeBase<T> {
     }
 
     pub fn leaf_layout() -> (Layout, usize) {
-        let base = Layout::new::<NodeBase<T>>();
+        let base = Layout::new::<NodeBase<T, B, C>>();
         let array = Layout::array::<T>(Self::LEAF_CAP).unwrap();
         let (layout, offset) = base.extend(array).unwrap();
         // Remember to finalize with `pad_to_align`!
@@ -99,19 +110,22 @@ impl<T> NodeBase<T> {
     }
 }
 
-impl<T> InternalNode<T> {
-    const UNINIT_NODE: MaybeUninit<NodePtr<T>> = MaybeUninit::uninit();
+impl<T, const B: usize, const C: usize> InternalNode<T, B, C> {
+    const UNINIT_NODE: MaybeUninit<NodePtr<T, B, C>> = MaybeUninit::uninit();
 
-    pub fn new(height: u8) -> NodePtr<T> {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(height: u8) -> NodePtr<T, B, C> {
         NonNull::from(Box::leak(Box::new(Self {
             base: NodeBase::new(height),
             lengths: FenwickTree::new(),
-            children: [Self::UNINIT_NODE; BRANCH_FACTOR],
+            children: [Self::UNINIT_NODE; B],
         })))
         .cast()
     }
 
-    pub fn from_child_array<const N: usize>(children: [RawNodeWithLen<T>; N]) -> NodePtr<T> {
+    pub fn from_child_array<const N: usize>(
+        children: [RawNodeWithLen<T, B, C>; N],
+    ) -> NodePtr<T, B, C> {
         let height = unsafe { children[0].1.as_ref().height + 1 };
         let boxed_children = Self::new(height);
         let mut children_mut = unsafe { InternalMut::new(boxed_children) };
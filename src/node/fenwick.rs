@@ -1,32 +1,31 @@
-use super::BRANCH_FACTOR;
-
+/// The lengths of the children of an internal node, stored as a Fenwick tree.
+///
+/// `B` must be a power of two for `child_containing_index` to visit every child.
 #[derive(Clone)]
-pub struct FenwickTree {
-    inner: [usize; BRANCH_FACTOR],
+pub struct FenwickTree<const B: usize> {
+    inner: [usize; B],
 }
 
-impl FenwickTree {
+impl<const B: usize> FenwickTree<B> {
     pub fn new() -> Self {
-        Self {
-            inner: [0; BRANCH_FACTOR],
-        }
+        Self { inner: [0; B] }
     }
 
-    pub fn from_array(array: [usize; BRANCH_FACTOR]) -> Self {
+    pub fn from_array(array: [usize; B]) -> Self {
         let mut this = Self { inner: array };
         this.init();
         this
     }
 
-    pub fn into_array(mut self) -> [usize; BRANCH_FACTOR] {
+    pub fn into_array(mut self) -> [usize; B] {
         self.fini();
         self.inner
     }
 
     pub fn child_containing_index(&self, index: &mut usize) -> usize {
         let mut i = 0;
-        for shift in 1..=BRANCH_FACTOR.trailing_zeros() {
-            let offset = BRANCH_FACTOR >> shift;
+        for shift in 1..=B.trailing_zeros() {
+            let offset = B >> shift;
             let v = self.inner[i + offset - 1];
             if v <= *index {
                 *index -= v;
@@ -77,7 +76,7 @@ impl FenwickTree {
 
     pub fn with_flat_lens<F, R>(&mut self, f: F) -> R
     where
-        F: FnOnce(&mut [usize; BRANCH_FACTOR]) -> R,
+        F: FnOnce(&mut [usize; B]) -> R,
     {
         self.fini();
         let ret = f(&mut self.inner);
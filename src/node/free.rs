@@ -0,0 +1,73 @@
+use core::mem;
+
+use crate::{
+    node::{
+        handle::{Internal, InternalMut, Leaf, LeafMut},
+        NodePtr,
+    },
+    utils::ArrayVecMut,
+};
+
+/// Drops all values in the detached tree rooted at `node` and frees its nodes.
+///
+/// If dropping a value panics, the rest of the tree is still dropped while
+/// unwinding.
+pub unsafe fn free_tree<T, const B: usize, const C: usize>(node: NodePtr<T, B, C>) {
+    unsafe { free_tree_with(node, |mut values| values.clear()) }
+}
+
+/// Calls `f` on the values of every leaf of the detached tree rooted at `node` from front
+/// to back and frees the nodes of the tree. `f` should take the values out
+/// of the leaf, anything it leaves behind is leaked.
+///
+/// The nodes are visited by following the parent pointers, so no extra
+/// memory is needed. If `f` panics, the values of the remaining leaves are
+/// dropped while unwinding.
+pub unsafe fn free_tree_with<T, const B: usize, const C: usize>(
+    mut node: NodePtr<T, B, C>,
+    mut f: impl FnMut(ArrayVecMut<T>),
+) {
+    struct DropGuard<T, const B: usize, const C: usize>(NodePtr<T, B, C>);
+
+    impl<T, const B: usize, const C: usize> Drop for DropGuard<T, B, C> {
+        fn drop(&mut self) {
+            // Every node before the current leaf has been freed, so this
+            // continues where the panic left off.
+            unsafe { free_tree(self.0) }
+        }
+    }
+
+    unsafe {
+        loop {
+            while node.as_ref().height() > 0 {
+                node = InternalMut::new(node).children()[0];
+            }
+
+            let guard = DropGuard(node);
+            f(LeafMut::new(node).values_mut());
+            mem::forget(guard);
+
+            loop {
+                let parent = node.as_ref().parent.map(|parent| {
+                    let index = node.as_ref().parent_index.assume_init();
+                    (parent, usize::from(index))
+                });
+                if node.as_ref().height() == 0 {
+                    Leaf::new(node).free();
+                } else {
+                    Internal::new(node).free();
+                }
+
+                let Some((parent, index)) = parent else {
+                    return;
+                };
+                let mut handle = InternalMut::new(parent);
+                if index + 1 < handle.len_children() {
+                    node = handle.children()[index + 1];
+                    break;
+                }
+                node = parent;
+            }
+        }
+    }
+}
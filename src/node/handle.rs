@@ -8,16 +8,26 @@ use core::{
 use alloc::boxed::Box;
 
 use crate::{
-    node::{InternalNode, NodeBase, NodePtr, RawNodeWithLen, BRANCH_FACTOR},
+    node::{InternalNode, NodeBase, NodePtr, RawNodeWithLen},
     ownership,
     utils::ArrayVecMut,
 };
 
 use super::fenwick::FenwickTree;
 
-impl<'a, T: 'a> LeafRef<'a, T> {
+impl<'a, T: 'a, const B: usize, const C: usize> LeafRef<'a, T, B, C> {
+    pub fn values(&self) -> &'a [T] {
+        // Like in `value_unchecked`, there are no mutable references into
+        // this leaf while we have a shared reference to it.
+        unsafe {
+            let (_, array_offset) = NodeBase::<T, B, C>::leaf_layout();
+            let array = self.node.as_ptr().cast::<u8>().add(array_offset).cast();
+            core::slice::from_raw_parts(array, self.len())
+        }
+    }
+
     pub unsafe fn value_unchecked(&self, index: usize) -> &'a T {
-        debug_assert!(self.len() <= NodeBase::<T>::LEAF_CAP);
+        debug_assert!(self.len() <= NodeBase::<T, B, C>::LEAF_CAP);
         debug_assert!(index < self.len());
 
         // We own a shared reference to this leaf, so there
@@ -25,7 +35,7 @@ impl<'a, T: 'a> LeafRef<'a, T> {
         // could cause aliasing problems with taking
         // a reference to the whole array.
         unsafe {
-            let (_, array_offset) = NodeBase::<T>::leaf_layout();
+            let (_, array_offset) = NodeBase::<T, B, C>::leaf_layout();
             &*self
                 .node
                 .as_ptr()
@@ -53,46 +63,51 @@ pub mod height {
     unsafe impl<H: Internal> Height for H {}
 }
 
-pub struct Node<O, H, T>
+pub struct Node<O, H, T, const B: usize, const C: usize>
 where
     H: height::Height,
     O: ownership::Ownership<T>,
 {
-    node: NodePtr<T>,
+    node: NodePtr<T, B, C>,
     _marker: PhantomData<(H, O)>,
 }
 
-pub type InternalRef<'a, T> = Node<ownership::Immut<'a>, height::Positive, T>;
-pub type InternalMut<'a, T> = Node<ownership::Mut<'a>, height::Positive, T>;
-pub type Internal<T> = Node<ownership::Owned, height::Positive, T>;
+pub type InternalRef<'a, T, const B: usize, const C: usize> =
+    Node<ownership::Immut<'a>, height::Positive, T, B, C>;
+pub type InternalMut<'a, T, const B: usize, const C: usize> =
+    Node<ownership::Mut<'a>, height::Positive, T, B, C>;
+pub type Internal<T, const B: usize, const C: usize> =
+    Node<ownership::Owned, height::Positive, T, B, C>;
 
-pub type LeafRef<'a, T> = Node<ownership::Immut<'a>, height::Zero, T>;
-pub type LeafMut<'a, T> = Node<ownership::Mut<'a>, height::Zero, T>;
-pub type Leaf<T> = Node<ownership::Owned, height::Zero, T>;
+pub type LeafRef<'a, T, const B: usize, const C: usize> =
+    Node<ownership::Immut<'a>, height::Zero, T, B, C>;
+pub type LeafMut<'a, T, const B: usize, const C: usize> =
+    Node<ownership::Mut<'a>, height::Zero, T, B, C>;
+pub type Leaf<T, const B: usize, const C: usize> = Node<ownership::Owned, height::Zero, T, B, C>;
 
-impl<O, H, T> Node<O, H, T>
+impl<O, H, T, const B: usize, const C: usize> Node<O, H, T, B, C>
 where
     H: height::Height,
     O: ownership::Ownership<T>,
 {
-    pub unsafe fn new(ptr: NodePtr<T>) -> Self {
+    pub unsafe fn new(ptr: NodePtr<T, B, C>) -> Self {
         Self {
             node: ptr,
             _marker: PhantomData,
         }
     }
 
-    pub fn node_ptr(&mut self) -> NodePtr<T> {
+    pub fn node_ptr(&mut self) -> NodePtr<T, B, C> {
         self.node
     }
 }
 
-impl<O, H, T> Node<O, H, T>
+impl<O, H, T, const B: usize, const C: usize> Node<O, H, T, B, C>
 where
     H: height::Height,
     O: ownership::Mutable<T>,
 {
-    fn reborrow(&mut self) -> Node<ownership::Mut, H, T> {
+    fn reborrow(&mut self) -> Node<ownership::Mut<'_>, H, T, B, C> {
         Node {
             node: self.node,
             _marker: PhantomData,
@@ -100,14 +115,15 @@ where
     }
 }
 
-impl<'a, O, H, T: 'a> Node<O, H, T>
+impl<'a, O, H, T: 'a, const B: usize, const C: usize> Node<O, H, T, B, C>
 where
     H: height::Height,
     O: ownership::Reference<'a, T>,
 {
-    pub fn into_parent_and_index2(mut self) -> Option<(Node<O, height::Positive, T>, usize)> {
+    pub fn into_parent_and_index2(mut self) -> Option<(Node<O, height::Positive, T, B, C>, usize)> {
         unsafe {
-            let parent = Node::<O, height::Positive, T>::new(self.node_ptr().as_ref().parent?);
+            let parent =
+                Node::<O, height::Positive, T, B, C>::new(self.node_ptr().as_ref().parent?);
             Some((
                 parent,
                 self.node_ptr().as_ref().parent_index.assume_init().into(),
@@ -116,13 +132,13 @@ where
     }
 }
 
-impl<'a, O, T: 'a> Node<O, height::Zero, T>
+impl<'a, O, T: 'a, const B: usize, const C: usize> Node<O, height::Zero, T, B, C>
 where
     O: ownership::Reference<'a, T>,
 {
-    pub fn into_parent_and_index3(mut self) -> Option<(Node<O, height::One, T>, usize)> {
+    pub fn into_parent_and_index3(mut self) -> Option<(Node<O, height::One, T, B, C>, usize)> {
         unsafe {
-            let parent = Node::<O, height::One, T>::new((*self.node_ptr().as_ptr()).parent?);
+            let parent = Node::<O, height::One, T, B, C>::new((*self.node_ptr().as_ptr()).parent?);
             Some((
                 parent,
                 (*self.node_ptr().as_ptr())
@@ -134,34 +150,40 @@ where
     }
 }
 
-impl<O, T> Node<O, height::Zero, T>
+impl<O, T, const B: usize, const C: usize> Node<O, height::Zero, T, B, C>
 where
     O: ownership::Ownership<T>,
 {
+    const UNDERFULL_LEN: usize = (NodeBase::<T, B, C>::LEAF_CAP - 1) / 2;
+
     pub fn len(&self) -> usize {
         unsafe { usize::from(self.node.as_ref().children_len) }
     }
+
+    pub fn is_underfull(&self) -> bool {
+        self.len() <= Self::UNDERFULL_LEN
+    }
 }
 
-impl<'a, T: 'a> LeafMut<'a, T> {
+impl<'a, T: 'a, const B: usize, const C: usize> LeafMut<'a, T, B, C> {
     pub fn values_mut(&mut self) -> ArrayVecMut<T> {
         unsafe {
-            let (_, offset) = NodeBase::<T>::leaf_layout();
+            let (_, offset) = NodeBase::<T, B, C>::leaf_layout();
             let array = self.node.as_ptr().cast::<u8>().add(offset).cast();
             ArrayVecMut::new(
                 array,
                 addr_of_mut!((*self.node.as_ptr()).children_len).cast(),
-                NodeBase::<T>::LEAF_CAP as u16,
+                NodeBase::<T, B, C>::LEAF_CAP as u16,
             )
         }
     }
 
     pub unsafe fn into_value_unchecked_mut(self, index: usize) -> &'a mut T {
         let len = self.len();
-        debug_assert!(len <= NodeBase::<T>::LEAF_CAP);
+        debug_assert!(len <= NodeBase::<T, B, C>::LEAF_CAP);
         debug_assert!(index < len);
         unsafe {
-            let (_, offset) = NodeBase::<T>::leaf_layout();
+            let (_, offset) = NodeBase::<T, B, C>::leaf_layout();
             &mut *self
                 .node
                 .as_ptr()
@@ -172,15 +194,15 @@ impl<'a, T: 'a> LeafMut<'a, T> {
         }
     }
 
-    fn is_full(&self) -> bool {
-        self.len() == NodeBase::<T>::LEAF_CAP
+    pub fn is_full(&self) -> bool {
+        self.len() == NodeBase::<T, B, C>::LEAF_CAP
     }
 
-    pub fn insert_value(&mut self, index: usize, value: T) -> Option<SplitResult<T>> {
+    pub fn insert_value(&mut self, index: usize, value: T) -> Option<SplitResult<T, B, C>> {
         assert!(index <= self.len());
 
         if self.is_full() {
-            Some(if index <= NodeBase::<T>::LEAF_CAP / 2 {
+            Some(if index <= NodeBase::<T, B, C>::LEAF_CAP / 2 {
                 SplitResult::Left(self.split_and_insert_left(index, value))
             } else {
                 SplitResult::Right(self.split_and_insert_right(index, value))
@@ -191,35 +213,40 @@ impl<'a, T: 'a> LeafMut<'a, T> {
         }
     }
 
-    fn split_and_insert_left(&mut self, index: usize, value: T) -> RawNodeWithLen<T> {
-        let split_index = NodeBase::<T>::LEAF_CAP / 2;
-        let new_node = NodeBase::new_leaf();
-        let mut new_leaf = unsafe { LeafMut::new(new_node) };
-        self.values_mut().split(split_index, new_leaf.values_mut());
+    fn split_and_insert_left(&mut self, index: usize, value: T) -> RawNodeWithLen<T, B, C> {
+        let split_index = NodeBase::<T, B, C>::LEAF_CAP / 2;
+        let new_leaf = self.split_off(split_index);
         self.values_mut().insert(index, value);
+        new_leaf
+    }
+
+    fn split_and_insert_right(&mut self, index: usize, value: T) -> RawNodeWithLen<T, B, C> {
+        let split_index = (NodeBase::<T, B, C>::LEAF_CAP - 1) / 2 + 1;
+        let RawNodeWithLen(_, new_node) = self.split_off(split_index);
+        let mut new_leaf = unsafe { LeafMut::new(new_node) };
+        new_leaf.values_mut().insert(index - self.len(), value);
         RawNodeWithLen(new_leaf.len(), new_node)
     }
 
-    fn split_and_insert_right(&mut self, index: usize, value: T) -> RawNodeWithLen<T> {
-        let split_index = (NodeBase::<T>::LEAF_CAP - 1) / 2 + 1;
+    /// Moves the values at `index..` into a new leaf and returns it.
+    pub fn split_off(&mut self, index: usize) -> RawNodeWithLen<T, B, C> {
         let new_node = NodeBase::new_leaf();
         let mut new_leaf = unsafe { LeafMut::new(new_node) };
-        self.values_mut().split(split_index, new_leaf.values_mut());
-        new_leaf.values_mut().insert(index - self.len(), value);
+        self.values_mut().split(index, new_leaf.values_mut());
         RawNodeWithLen(new_leaf.len(), new_node)
     }
 }
 
-impl<O, T> Node<O, height::One, T>
+impl<O, T, const B: usize, const C: usize> Node<O, height::One, T, B, C>
 where
     O: ownership::Mutable<T>,
 {
-    pub fn child_mut(&mut self, index: usize) -> LeafMut<T> {
+    pub fn child_mut(&mut self, index: usize) -> LeafMut<'_, T, B, C> {
         let ptr = unsafe { (*self.internal_ptr()).children.as_mut_ptr() };
         unsafe { LeafMut::new(ptr.add(index).read().assume_init()) }
     }
 
-    pub fn child_pair_at(&mut self, index: usize) -> [LeafMut<T>; 2] {
+    pub fn child_pair_at(&mut self, index: usize) -> [LeafMut<'_, T, B, C>; 2] {
         let ptr = unsafe { (*self.internal_ptr()).children.as_mut_ptr() };
         [
             unsafe { LeafMut::new(ptr.add(index).read().assume_init()) },
@@ -265,11 +292,11 @@ where
     }
 }
 
-impl<O, T> Node<O, height::TwoOrMore, T>
+impl<O, T, const B: usize, const C: usize> Node<O, height::TwoOrMore, T, B, C>
 where
     O: ownership::Mutable<T>,
 {
-    pub unsafe fn new_parent_of_internal(node: NodePtr<T>) -> Self {
+    pub unsafe fn new_parent_of_internal(node: NodePtr<T, B, C>) -> Self {
         Self {
             node,
             _marker: PhantomData,
@@ -290,7 +317,10 @@ where
         is_child_underfull
     }
 
-    pub fn child_mut(&mut self, index: usize) -> Node<ownership::Mut, height::Positive, T> {
+    pub fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Node<ownership::Mut<'_>, height::Positive, T, B, C> {
         let ptr = unsafe { (*self.internal_ptr()).children.as_mut_ptr() };
         Node {
             node: unsafe { ptr.add(index).read().assume_init().cast() },
@@ -301,7 +331,7 @@ where
     pub fn child_pair_at(
         &mut self,
         index: usize,
-    ) -> [Node<ownership::Mut, height::Positive, T>; 2] {
+    ) -> [Node<ownership::Mut<'_>, height::Positive, T, B, C>; 2] {
         let ptr = unsafe { (*self.internal_ptr()).children.as_mut_ptr() };
         [
             Node {
@@ -356,23 +386,22 @@ where
     }
 }
 
-impl<T> Leaf<T> {
+impl<T, const B: usize, const C: usize> Leaf<T, B, C> {
     pub fn free(self) {
-        let (layout, _) = NodeBase::<T>::leaf_layout();
+        let (layout, _) = NodeBase::<T, B, C>::leaf_layout();
         unsafe { alloc::alloc::dealloc(self.node.cast().as_ptr(), layout) }
     }
 }
 
-impl<T> Internal<T> {
+impl<T, const B: usize, const C: usize> Internal<T, B, C> {
     pub fn free(self) {
         // debug_assert_eq!(self.node.base.children_len, 0);
         // debug_assert_eq!(self.node.len(), 0);
-        unsafe { Box::from_raw(self.node.cast::<InternalNode<T>>().as_ptr()) };
+        drop(unsafe { Box::from_raw(self.node.cast::<InternalNode<T, B, C>>().as_ptr()) });
     }
 }
 
-impl<'a, T: 'a> LeafMut<'a, T> {
-    const UNDERFULL_LEN: usize = (NodeBase::<T>::LEAF_CAP - 1) / 2;
+impl<'a, T: 'a, const B: usize, const C: usize> LeafMut<'a, T, B, C> {
     pub fn remove_child(&mut self, index: usize) -> T {
         self.values_mut().remove(index)
     }
@@ -388,22 +417,36 @@ impl<'a, T: 'a> LeafMut<'a, T> {
     fn pop_back_child(&mut self) -> T {
         self.remove_child(self.len() - 1)
     }
-    pub fn is_underfull(&self) -> bool {
-        self.len() <= Self::UNDERFULL_LEN
-    }
     fn is_almost_underfull(&self) -> bool {
         self.len() <= Self::UNDERFULL_LEN + 1
     }
+
+    /// Merges `next` into `self` if their values fit into a single leaf and
+    /// returns `true`. Otherwise moves values across the seam until neither
+    /// leaf is underfull.
+    pub fn join_with_next(&mut self, next: &mut LeafMut<'a, T, B, C>) -> bool {
+        if self.len() + next.len() <= NodeBase::<T, B, C>::LEAF_CAP {
+            self.values_mut().append(next.values_mut());
+            return true;
+        }
+        while self.is_underfull() {
+            self.push_back_child(next.pop_front_child());
+        }
+        while next.is_underfull() {
+            next.push_front_child(self.pop_back_child());
+        }
+        false
+    }
 }
 
-impl<O, H, T> Node<O, H, T>
+impl<O, H, T, const B: usize, const C: usize> Node<O, H, T, B, C>
 where
     H: height::Internal,
     O: ownership::Ownership<T>,
 {
-    pub const UNDERFULL_LEN: usize = (BRANCH_FACTOR - 1) / 2;
+    pub const UNDERFULL_LEN: usize = (B - 1) / 2;
 
-    fn node(&self) -> &InternalNode<T> {
+    fn node(&self) -> &InternalNode<T, B, C> {
         unsafe { self.node.cast().as_ref() }
     }
 
@@ -415,15 +458,15 @@ where
         self.len_children() == 1
     }
 
-    fn is_full(&self) -> bool {
-        self.len_children() == BRANCH_FACTOR
+    pub fn is_full(&self) -> bool {
+        self.len_children() == B
     }
 
     pub fn is_underfull(&self) -> bool {
         self.len_children() <= Self::UNDERFULL_LEN
     }
 
-    pub fn internal_ptr(&mut self) -> *mut InternalNode<T> {
+    pub fn internal_ptr(&mut self) -> *mut InternalNode<T, B, C> {
         self.node.cast().as_ptr()
     }
 
@@ -436,30 +479,30 @@ where
     }
 }
 
-impl<O, H, T> Node<O, H, T>
+impl<O, H, T, const B: usize, const C: usize> Node<O, H, T, B, C>
 where
     H: height::Internal,
     O: ownership::Mutable<T>,
 {
-    unsafe fn push_front_child(&mut self, child: RawNodeWithLen<T>) {
+    unsafe fn push_front_child(&mut self, child: RawNodeWithLen<T, B, C>) {
         unsafe {
             self.push_front_length(child.0);
             self.children().insert(0, child.1);
             self.set_parent_links(0..);
         }
     }
-    pub unsafe fn push_back_child(&mut self, child: RawNodeWithLen<T>) {
+    pub unsafe fn push_back_child(&mut self, child: RawNodeWithLen<T, B, C>) {
         unsafe { self.push_back_length(child.0) };
         self.children().insert(self.len_children(), child.1);
         self.set_parent_links(self.len_children() - 1..);
     }
-    unsafe fn pop_front_child(&mut self) -> RawNodeWithLen<T> {
+    unsafe fn pop_front_child(&mut self) -> RawNodeWithLen<T, B, C> {
         let node_len = unsafe { self.pop_front_length() };
         let node = self.children().remove(0);
         self.set_parent_links(0..);
         RawNodeWithLen(node_len, node)
     }
-    unsafe fn pop_back_child(&mut self) -> RawNodeWithLen<T> {
+    pub unsafe fn pop_back_child(&mut self) -> RawNodeWithLen<T, B, C> {
         let last_len = unsafe { self.pop_back_length() };
         let last = self.children().remove(self.len_children() - 1);
         RawNodeWithLen(last_len, last)
@@ -474,11 +517,30 @@ where
         self.set_parent_links(self_old_len..);
     }
 
-    pub fn internal_mut(&mut self) -> &mut InternalNode<T> {
+    /// Merges the children of `next` into `self` if they fit into a single node
+    /// and returns `true`. Otherwise moves children across the seam until
+    /// neither node is underfull.
+    pub unsafe fn join_with_next(&mut self, mut next: Self) -> bool {
+        unsafe {
+            if self.len_children() + next.len_children() <= B {
+                self.append_children(next);
+                return true;
+            }
+            while self.is_underfull() {
+                self.push_back_child(next.pop_front_child());
+            }
+            while next.is_underfull() {
+                next.push_front_child(self.pop_back_child());
+            }
+            false
+        }
+    }
+
+    pub fn internal_mut(&mut self) -> &mut InternalNode<T, B, C> {
         unsafe { self.node.cast().as_mut() }
     }
 
-    fn lengths_mut(&mut self) -> &mut FenwickTree {
+    fn lengths_mut(&mut self) -> &mut FenwickTree<B> {
         &mut self.internal_mut().lengths
     }
 
@@ -496,7 +558,7 @@ where
         }
     }
 
-    unsafe fn append_lengths<'b>(&'b mut self, mut other: Node<ownership::Mut<'b>, H, T>) {
+    unsafe fn append_lengths<'b>(&'b mut self, mut other: Node<ownership::Mut<'b>, H, T, B, C>) {
         let self_len_children = self.len_children();
         let other_len_children = other.len_children();
         let other_lens = other.lengths_mut().clone().into_array();
@@ -514,9 +576,9 @@ where
             ptr::copy(
                 lens_ptr.add(index + 2),
                 lens_ptr.add(index + 1),
-                BRANCH_FACTOR - index - 2,
+                B - index - 2,
             );
-            lens[BRANCH_FACTOR - 1] = 0;
+            lens[B - 1] = 0;
         });
     }
 
@@ -530,10 +592,10 @@ where
         });
     }
 
-    unsafe fn split_lengths(&mut self, index: usize) -> FenwickTree {
+    unsafe fn split_lengths(&mut self, index: usize) -> FenwickTree<B> {
         let len_children = self.len_children();
         self.lengths_mut().with_flat_lens(|lens| {
-            let mut other_array = [0; BRANCH_FACTOR];
+            let mut other_array = [0; B];
             for i in index..len_children {
                 other_array[i - index] = lens[i];
                 lens[i] = 0;
@@ -549,7 +611,7 @@ where
             for i in 1..len_children {
                 lens[i - 1] = lens[i];
             }
-            *lens.last_mut().unwrap() = 0;
+            lens[len_children - 1] = 0;
             first_len
         })
     }
@@ -581,21 +643,23 @@ where
         self.lengths_mut().add_wrapping(index, amount);
     }
 
-    fn node_mut(&mut self) -> &mut InternalNode<T> {
+    fn node_mut(&mut self) -> &mut InternalNode<T, B, C> {
         unsafe { self.node.cast().as_mut() }
     }
 
-    pub fn children(&mut self) -> ArrayVecMut<NodePtr<T>> {
+    pub fn children(&mut self) -> ArrayVecMut<NodePtr<T, B, C>> {
         unsafe {
             ArrayVecMut::new(
                 addr_of_mut!((*self.internal_ptr()).children).cast(),
                 addr_of_mut!(self.node_mut().base.children_len).cast(),
-                BRANCH_FACTOR as u16,
+                B as u16,
             )
         }
     }
 
-    pub fn into_parent_and_index<'a>(mut self) -> Option<(Node<O, height::TwoOrMore, T>, usize)>
+    pub fn into_parent_and_index<'a>(
+        mut self,
+    ) -> Option<(Node<O, height::TwoOrMore, T, B, C>, usize)>
     where
         T: 'a,
         O: ownership::Reference<'a, T>,
@@ -609,7 +673,7 @@ where
         }
     }
 
-    pub unsafe fn into_child_containing_index(mut self, index: &mut usize) -> NodePtr<T> {
+    pub unsafe fn into_child_containing_index(mut self, index: &mut usize) -> NodePtr<T, B, C> {
         let i = self.node().lengths.child_containing_index(index);
         debug_assert!(i < self.len_children());
         unsafe { self.internal_mut().children[i].assume_init() }
@@ -618,24 +682,51 @@ where
     pub unsafe fn insert_split_of_child(
         &mut self,
         index: usize,
-        node: RawNodeWithLen<T>,
-    ) -> Option<RawNodeWithLen<T>> {
+        node: RawNodeWithLen<T, B, C>,
+    ) -> Option<RawNodeWithLen<T, B, C>> {
         unsafe {
             self.add_length_wrapping(index, node.0.wrapping_neg());
+            self.insert_child(index + 1, node)
+        }
+    }
+
+    /// Like `insert_split_of_child`, but `node` is placed before the child at `index`.
+    pub unsafe fn insert_split_of_child_before(
+        &mut self,
+        index: usize,
+        node: RawNodeWithLen<T, B, C>,
+    ) -> Option<RawNodeWithLen<T, B, C>> {
+        unsafe {
+            self.add_length_wrapping(index, node.0.wrapping_neg());
+            self.insert_child(index, node)
+        }
+    }
+
+    unsafe fn insert_child(
+        &mut self,
+        index: usize,
+        node: RawNodeWithLen<T, B, C>,
+    ) -> Option<RawNodeWithLen<T, B, C>> {
+        unsafe {
             if self.is_full() {
-                use core::cmp::Ordering::Less;
-                Some(match index.cmp(&Self::UNDERFULL_LEN) {
-                    Less => self.split_and_insert_left(index + 1, node),
-                    _ => self.split_and_insert_right(index + 1, node),
+                Some(if index <= Self::UNDERFULL_LEN {
+                    self.split_and_insert_left(index, node)
+                } else {
+                    self.split_and_insert_right(index, node)
                 })
             } else {
-                self.insert_fitting(index + 1, node);
+                self.insert_fitting(index, node);
                 None
             }
         }
     }
 
-    unsafe fn insert_fitting(&mut self, index: usize, node: RawNodeWithLen<T>) {
+    pub unsafe fn replace_child(&mut self, index: usize, node: NodePtr<T, B, C>) {
+        self.children()[index] = node;
+        self.set_parent_links(index..);
+    }
+
+    unsafe fn insert_fitting(&mut self, index: usize, node: RawNodeWithLen<T, B, C>) {
         debug_assert!(!self.is_full());
         unsafe {
             self.insert_length(index, node.0);
@@ -647,38 +738,47 @@ where
     unsafe fn split_and_insert_left(
         &mut self,
         index: usize,
-        node: RawNodeWithLen<T>,
-    ) -> RawNodeWithLen<T> {
-        let split_index = Self::UNDERFULL_LEN;
-
-        let new_sibling_node = InternalNode::<T>::new(self.node().base.height);
-        let mut new_sibling = unsafe { Node::<ownership::Mut, H, T>::new(new_sibling_node) };
-
+        node: RawNodeWithLen<T, B, C>,
+    ) -> RawNodeWithLen<T, B, C> {
         unsafe {
-            *new_sibling.lengths_mut() = self.split_lengths(split_index);
-            self.children().split(split_index, new_sibling.children());
+            let new_sibling = self.split_off(Self::UNDERFULL_LEN);
             self.insert_fitting(index, node);
-        };
-
-        new_sibling.set_parent_links(0..);
-        RawNodeWithLen(new_sibling.len(), new_sibling_node)
+            new_sibling
+        }
     }
 
     unsafe fn split_and_insert_right(
         &mut self,
         index: usize,
-        node: RawNodeWithLen<T>,
-    ) -> RawNodeWithLen<T> {
+        node: RawNodeWithLen<T, B, C>,
+    ) -> RawNodeWithLen<T, B, C> {
         let split_index = Self::UNDERFULL_LEN + 1;
 
-        let new_sibling_node = InternalNode::<T>::new(self.node().base.height);
+        unsafe {
+            let RawNodeWithLen(_, new_sibling_node) = self.split_off(split_index);
+            let mut new_sibling = Node::<ownership::Mut, H, T, B, C>::new(new_sibling_node);
+            new_sibling.insert_fitting(index - split_index, node);
+            RawNodeWithLen(new_sibling.len(), new_sibling_node)
+        }
+    }
+
+    /// Reverses the order of the children.
+    pub fn reverse_children(&mut self) {
+        let len_children = self.len_children();
+        self.lengths_mut()
+            .with_flat_lens(|lens| lens[..len_children].reverse());
+        self.children()[..].reverse();
+        self.set_parent_links(0..);
+    }
 
-        let mut new_sibling = unsafe { Node::<ownership::Mut, H, T>::new(new_sibling_node) };
+    /// Moves the children at `index..` into a new sibling node and returns it.
+    pub unsafe fn split_off(&mut self, index: usize) -> RawNodeWithLen<T, B, C> {
+        let new_sibling_node = InternalNode::<T, B, C>::new(self.node().base.height);
+        let mut new_sibling = unsafe { Node::<ownership::Mut, H, T, B, C>::new(new_sibling_node) };
 
         unsafe {
-            *new_sibling.lengths_mut() = self.split_lengths(split_index);
-            self.children().split(split_index, new_sibling.children());
-            new_sibling.insert_fitting(index - split_index, node);
+            *new_sibling.lengths_mut() = self.split_lengths(index);
+            self.children().split(index, new_sibling.children());
         }
 
         new_sibling.set_parent_links(0..);
@@ -695,7 +795,7 @@ where
     }
 }
 
-pub enum SplitResult<T> {
-    Left(RawNodeWithLen<T>),
-    Right(RawNodeWithLen<T>),
+pub enum SplitResult<T, const B: usize, const C: usize> {
+    Left(RawNodeWithLen<T, B, C>),
+    Right(RawNodeWithLen<T, B, C>),
 }
@@ -0,0 +1,140 @@
+use crate::node::{
+    handle::{Internal, InternalMut, InternalRef, Leaf, LeafMut, LeafRef},
+    InternalNode, NodePtr, RawNodeWithLen,
+};
+
+/// Concatenates two non-empty trees and returns the root of the result.
+///
+/// The root of the shorter tree is adopted as a child by the taller tree at
+/// the matching height, so only the spine between those two levels is
+/// restructured instead of the whole height of the taller tree.
+pub unsafe fn join<T, const B: usize, const C: usize>(
+    left: RawNodeWithLen<T, B, C>,
+    right: RawNodeWithLen<T, B, C>,
+) -> NodePtr<T, B, C> {
+    unsafe {
+        let total_len = left.0 + right.0;
+        let left_height = left.1.as_ref().height();
+        let right_height = right.1.as_ref().height();
+
+        if left_height >= right_height {
+            let mut seam = left.1;
+            for _ in right_height..left_height {
+                let mut handle = InternalMut::new(seam);
+                seam = handle.children()[handle.len_children() - 1];
+            }
+            adopt(left.1, total_len, seam, right, false)
+        } else {
+            let mut seam = right.1;
+            for _ in left_height..right_height {
+                seam = InternalMut::new(seam).children()[0];
+            }
+            adopt(right.1, total_len, seam, left, true)
+        }
+    }
+}
+
+/// Places the root of `other` next to `seam`, which is a node of the same
+/// height in the tree rooted at `root`. Returns the new root.
+unsafe fn adopt<T, const B: usize, const C: usize>(
+    root: NodePtr<T, B, C>,
+    total_len: usize,
+    seam: NodePtr<T, B, C>,
+    other: RawNodeWithLen<T, B, C>,
+    other_is_left: bool,
+) -> NodePtr<T, B, C> {
+    unsafe {
+        // Whether `other` gets merged into `seam` or placed next to it,
+        // all of its elements end up below the ancestors of `seam`.
+        let mut node = seam;
+        while let Some(parent) = node.as_ref().parent {
+            let index = node.as_ref().parent_index.assume_init().into();
+            InternalMut::new(parent).add_length_wrapping(index, other.0);
+            node = parent;
+        }
+
+        let (left, right) = if other_is_left {
+            (other.1, seam)
+        } else {
+            (seam, other.1)
+        };
+
+        if join_with_next(left, right) {
+            let parent = seam.as_ref().parent;
+            if other_is_left {
+                // `left` is the node that survived, so it takes the place of `seam`.
+                if let Some(parent) = parent {
+                    let index = seam.as_ref().parent_index.assume_init().into();
+                    InternalMut::new(parent).replace_child(index, left);
+                }
+            }
+            free_node(right);
+            return if parent.is_some() { root } else { left };
+        }
+
+        let Some(parent) = seam.as_ref().parent else {
+            return InternalNode::from_child_array([
+                RawNodeWithLen(node_len(left), left),
+                RawNodeWithLen(node_len(right), right),
+            ]);
+        };
+
+        let index = seam.as_ref().parent_index.assume_init().into();
+        let mut parent = InternalMut::new(parent);
+        let other = RawNodeWithLen(node_len(other.1), other.1);
+        let mut to_insert = if other_is_left {
+            parent.insert_split_of_child_before(index, other)
+        } else {
+            parent.insert_split_of_child(index, other)
+        };
+        let mut new_parent = parent.into_parent_and_index2();
+
+        while let Some(new_node) = to_insert {
+            if let Some((mut parent, child_index)) = new_parent {
+                to_insert = parent.insert_split_of_child(child_index, new_node);
+                new_parent = parent.into_parent_and_index2();
+            } else {
+                return InternalNode::from_child_array([
+                    RawNodeWithLen(total_len - new_node.0, root),
+                    new_node,
+                ]);
+            }
+        }
+
+        root
+    }
+}
+
+/// Balances two adjacent nodes of the same height, see `LeafMut::join_with_next`.
+unsafe fn join_with_next<T, const B: usize, const C: usize>(
+    left: NodePtr<T, B, C>,
+    right: NodePtr<T, B, C>,
+) -> bool {
+    unsafe {
+        if left.as_ref().height() == 0 {
+            LeafMut::new(left).join_with_next(&mut LeafMut::new(right))
+        } else {
+            InternalMut::new(left).join_with_next(InternalMut::new(right))
+        }
+    }
+}
+
+unsafe fn node_len<T, const B: usize, const C: usize>(node: NodePtr<T, B, C>) -> usize {
+    unsafe {
+        if node.as_ref().height() == 0 {
+            LeafRef::new(node).len()
+        } else {
+            InternalRef::new(node).len()
+        }
+    }
+}
+
+unsafe fn free_node<T, const B: usize, const C: usize>(node: NodePtr<T, B, C>) {
+    unsafe {
+        if node.as_ref().height() == 0 {
+            Leaf::new(node).free();
+        } else {
+            Internal::new(node).free();
+        }
+    }
+}
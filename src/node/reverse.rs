@@ -0,0 +1,22 @@
+use crate::node::{
+    handle::{InternalMut, LeafMut},
+    NodePtr,
+};
+
+/// Reverses the order of the elements in the tree rooted at `node`.
+///
+/// Every node keeps its number of children, so the tree stays balanced
+/// without moving anything between nodes.
+pub unsafe fn reverse<T, const B: usize, const C: usize>(node: NodePtr<T, B, C>) {
+    unsafe {
+        if node.as_ref().height() == 0 {
+            LeafMut::new(node).values_mut()[..].reverse();
+            return;
+        }
+        let mut handle = InternalMut::new(node);
+        handle.reverse_children();
+        for &child in &handle.children()[..] {
+            reverse(child);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::node::{
+    handle::{InternalRef, LeafRef},
+    NodePtr,
+};
+
+/// Returns the number of leading elements of the tree rooted at `node` for
+/// which `pred` returns `true`. `pred` must be `true` for a prefix of the
+/// elements and `false` for the rest.
+///
+/// Each internal node on the way down is searched by probing the first
+/// element of its children, so no index has to be descended to from the root.
+pub unsafe fn partition_point<T, P, const B: usize, const C: usize>(
+    node: NodePtr<T, B, C>,
+    pred: P,
+) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    unsafe { partition_point_in_leaf(node, pred).0 }
+}
+
+/// Like `partition_point`, but also returns the leaf where the search ended
+/// and the partition point within it. The index within the leaf is equal to
+/// the length of the leaf if the partition point is right after it.
+pub unsafe fn partition_point_in_leaf<T, P, const B: usize, const C: usize>(
+    mut node: NodePtr<T, B, C>,
+    mut pred: P,
+) -> (usize, NodePtr<T, B, C>, usize)
+where
+    P: FnMut(&T) -> bool,
+{
+    unsafe {
+        let mut offset = 0;
+        while node.as_ref().height() > 0 {
+            let handle = InternalRef::<T, B, C>::new(node);
+
+            // The first child is never probed, since the partition point
+            // can't be before it.
+            let (mut lo, mut hi) = (1, handle.len_children());
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                if pred(first_value(child(node, mid))) {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+
+            offset += handle.sum_lens_below(lo - 1);
+            node = child(node, lo - 1);
+        }
+
+        let leaf = LeafRef::new(node);
+        let (mut lo, mut hi) = (0, leaf.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if pred(leaf.value_unchecked(mid)) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        (offset + lo, node, lo)
+    }
+}
+
+unsafe fn child<T, const B: usize, const C: usize>(
+    node: NodePtr<T, B, C>,
+    index: usize,
+) -> NodePtr<T, B, C> {
+    unsafe {
+        let mut handle = InternalRef::new(node);
+        (*handle.internal_ptr())
+            .children
+            .get_unchecked(index)
+            .assume_init()
+    }
+}
+
+unsafe fn first_value<'a, T, const B: usize, const C: usize>(mut node: NodePtr<T, B, C>) -> &'a T {
+    unsafe {
+        while node.as_ref().height() > 0 {
+            node = child(node, 0);
+        }
+        LeafRef::new(node).value_unchecked(0)
+    }
+}
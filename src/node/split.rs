@@ -0,0 +1,98 @@
+use crate::node::{
+    handle::{Internal, InternalMut, Leaf, LeafMut},
+    join::join,
+    NodePtr, RawNodeWithLen,
+};
+
+/// Splits a non-empty tree into the trees holding the elements before and
+/// after `at`, which must be in `1..len`.
+///
+/// The leaf containing `at` is cut in two, and on the way back up each node
+/// on its path is cut into the children before and after the path. These
+/// fragments are valid trees by themselves and get joined onto the halves
+/// built so far, so only the nodes near the path are touched.
+pub unsafe fn split<T, const B: usize, const C: usize>(
+    root: RawNodeWithLen<T, B, C>,
+    mut at: usize,
+) -> (RawNodeWithLen<T, B, C>, RawNodeWithLen<T, B, C>) {
+    unsafe {
+        debug_assert!(0 < at && at < root.0);
+
+        let mut node = root.1;
+        for _ in 0..node.as_ref().height() {
+            node = InternalMut::new(node).into_child_containing_index(&mut at);
+        }
+        split_at_leaf(node, at)
+    }
+}
+
+/// Like `split`, but starts from the leaf `node` that contains the split
+/// point at `at`. There must be elements both before and after the split.
+pub unsafe fn split_at_leaf<T, const B: usize, const C: usize>(
+    node: NodePtr<T, B, C>,
+    at: usize,
+) -> (RawNodeWithLen<T, B, C>, RawNodeWithLen<T, B, C>) {
+    unsafe {
+        let mut new_parent = detach(node);
+        let mut leaf = LeafMut::new(node);
+        let mut right = leaf.split_off(at);
+        let mut left = None;
+        if leaf.len() == 0 {
+            Leaf::new(node).free();
+        } else {
+            left = Some(RawNodeWithLen(leaf.len(), node));
+        }
+
+        while let Some((parent, index)) = new_parent {
+            new_parent = detach(parent);
+            let mut parent_mut = InternalMut::new(parent);
+
+            if index + 1 < parent_mut.len_children() {
+                let after = into_tree(parent_mut.split_off(index + 1));
+                right = RawNodeWithLen(right.0 + after.0, join(right, after));
+            }
+
+            // The child at `index` is already part of `left` and `right`.
+            parent_mut.pop_back_child();
+            if index > 0 {
+                let before = into_tree(RawNodeWithLen(parent_mut.len(), parent));
+                left = Some(match left {
+                    Some(left) => RawNodeWithLen(before.0 + left.0, join(before, left)),
+                    None => before,
+                });
+            } else {
+                Internal::new(parent).free();
+            }
+        }
+
+        (left.unwrap_unchecked(), right)
+    }
+}
+
+/// Unlinks `node` from its parent and returns the parent and the index of `node` in it.
+unsafe fn detach<T, const B: usize, const C: usize>(
+    mut node: NodePtr<T, B, C>,
+) -> Option<(NodePtr<T, B, C>, usize)> {
+    unsafe {
+        let node = node.as_mut();
+        let parent = node.parent.take()?;
+        Some((parent, node.parent_index.assume_init().into()))
+    }
+}
+
+/// Turns a detached internal node into the root of a valid tree by replacing
+/// it with its child if it only has one.
+unsafe fn into_tree<T, const B: usize, const C: usize>(
+    node: RawNodeWithLen<T, B, C>,
+) -> RawNodeWithLen<T, B, C> {
+    unsafe {
+        let mut handle = InternalMut::new(node.1);
+        if !handle.is_singleton() {
+            return node;
+        }
+        let RawNodeWithLen(_, child) = handle.pop_back_child();
+        Internal::new(node.1).free();
+        detach(child);
+        RawNodeWithLen(node.0, child)
+    }
+}
@@ -9,3 +9,9 @@ pub fn panic_out_of_bounds(index: usize, len: usize) -> ! {
 pub fn panic_length_overflow() -> ! {
     panic!("length overflow");
 }
+
+#[cold]
+#[track_caller]
+pub fn panic_cursor_out_of_bounds(index: usize, offset: isize, len: usize) -> ! {
+    panic!("cursor out of bounds: the len is {len} but moving from {index} by {offset}");
+}
@@ -0,0 +1,71 @@
+use ::rayon::iter::{
+    plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+};
+
+use crate::{iter::Iter, BVec};
+
+/// A parallel iterator over references to the elements of a `BVec`, created
+/// by `par_iter`.
+///
+/// The work is split by moving a cursor to the split index, which only
+/// touches the nodes between the two halves, so every part can be handed to
+/// a different thread without copying.
+pub struct ParIter<'a, T, const B: usize, const C: usize> {
+    tree: &'a BVec<T, B, C>,
+}
+
+impl<'a, T: Sync, const B: usize, const C: usize> IntoParallelIterator for &'a BVec<T, B, C> {
+    type Iter = ParIter<'a, T, B, C>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter { tree: self }
+    }
+}
+
+impl<'a, T: Sync, const B: usize, const C: usize> ParallelIterator for ParIter<'a, T, B, C> {
+    type Item = &'a T;
+
+    fn drive_unindexed<R: UnindexedConsumer<Self::Item>>(self, consumer: R) -> R::Result {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.tree.len())
+    }
+}
+
+impl<'a, T: Sync, const B: usize, const C: usize> IndexedParallelIterator for ParIter<'a, T, B, C> {
+    fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    fn drive<R: Consumer<Self::Item>>(self, consumer: R) -> R::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(IterProducer {
+            iter: self.tree.iter(),
+        })
+    }
+}
+
+struct IterProducer<'a, T, const B: usize, const C: usize> {
+    iter: Iter<'a, T, B, C>,
+}
+
+impl<'a, T: Sync, const B: usize, const C: usize> Producer for IterProducer<'a, T, B, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, B, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (head, tail) = self.iter.split_at(index);
+        (Self { iter: head }, Self { iter: tail })
+    }
+}
@@ -0,0 +1,46 @@
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{Deserialize, Deserializer, SeqAccess, Visitor},
+    ser::{Serialize, SerializeSeq, Serializer},
+};
+
+use crate::{BVec, BVecBuilder};
+
+impl<T: Serialize, const B: usize, const C: usize> Serialize for BVec<T, B, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const B: usize, const C: usize> Deserialize<'de> for BVec<T, B, C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BVecVisitor<T, const B: usize, const C: usize>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>, const B: usize, const C: usize> Visitor<'de>
+            for BVecVisitor<T, B, C>
+        {
+            type Value = BVec<T, B, C>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                // The builder allocates every leaf exactly once when it is
+                // first needed, so there is nothing to gain from `size_hint`.
+                let mut builder = BVecBuilder::new_with_params();
+                while let Some(value) = seq.next_element()? {
+                    builder.push(value);
+                }
+                Ok(builder.finish())
+            }
+        }
+
+        deserializer.deserialize_seq(BVecVisitor(PhantomData))
+    }
+}
@@ -1,8 +1,35 @@
 use core::{
-    ops::{Index, IndexMut},
+    mem,
+    ops::{Bound, Index, IndexMut, RangeBounds},
     ptr, slice,
 };
 
+/// Converts `range` into the start and end indices of a range of a sequence
+/// of length `len`.
+///
+/// # Panics
+/// Panics if the range starts after it ends or if it ends after `len`.
+#[track_caller]
+pub fn resolve_range<R: RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Unbounded => 0,
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s
+            .checked_add(1)
+            .expect("attempted to index from after usize::MAX"),
+    };
+    let end = match range.end_bound() {
+        Bound::Unbounded => len,
+        Bound::Included(&e) => e
+            .checked_add(1)
+            .expect("attempted to index up to and including usize::MAX"),
+        Bound::Excluded(&e) => e,
+    };
+    assert!(start <= end, "range starts at {start} but ends at {end}");
+    assert!(end <= len, "range end {end} out of range for length {len}");
+    (start, end)
+}
+
 pub struct ArrayVecMut<T> {
     array: *mut T,
     len: *mut u16,
@@ -48,9 +75,13 @@ impl<T> ArrayVecMut<T> {
         }
     }
 
+    /// Moves the values at `index..` into the empty `other`, which must have
+    /// room for them. `index` can be anything in `0..=len`.
     pub fn split(&mut self, index: usize, other: Self) {
         let len = self.len();
         assert!(index <= len);
+        debug_assert_eq!(other.len(), 0);
+        debug_assert!(len - index <= usize::from(other.cap));
         let mut tail_len = len;
         tail_len -= index;
         let src = unsafe { self.array.add(index) };
@@ -62,6 +93,28 @@ impl<T> ArrayVecMut<T> {
         }
     }
 
+    /// Drops all values and sets the length to zero. If dropping a value
+    /// panics, the remaining values are still dropped.
+    pub fn clear(&mut self) {
+        let len = self.len();
+        unsafe {
+            // Set the length first so that nothing gets dropped twice.
+            *self.len = 0;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.array, len));
+        }
+    }
+
+    /// Moves all values out into the returned iterator and sets the length
+    /// to zero. Values not yielded by the iterator are dropped with it.
+    pub fn drain(&mut self) -> ArrayVecDrain<T> {
+        let len = self.len();
+        unsafe { *self.len = 0 };
+        ArrayVecDrain {
+            next: self.array,
+            remaining: len,
+        }
+    }
+
     pub fn append(&mut self, other: Self) {
         assert!(self.len() + other.len() <= usize::from(self.cap));
         let src = other.array;
@@ -74,6 +127,35 @@ impl<T> ArrayVecMut<T> {
     }
 }
 
+pub struct ArrayVecDrain<T> {
+    next: *mut T,
+    remaining: usize,
+}
+
+impl<T> Iterator for ArrayVecDrain<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        (self.remaining > 0).then(|| unsafe {
+            let value = self.next.read();
+            self.next = self.next.add(1);
+            self.remaining -= 1;
+            value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> Drop for ArrayVecDrain<T> {
+    fn drop(&mut self) {
+        let remaining = mem::replace(&mut self.remaining, 0);
+        unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.next, remaining)) };
+    }
+}
+
 impl<T, I> Index<I> for ArrayVecMut<T>
 where
     [T]: Index<I>,
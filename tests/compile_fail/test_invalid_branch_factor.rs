@@ -0,0 +1,7 @@
+use bvec::BVec;
+
+const TREE: BVec<i32, 5, 256> = BVec::new_with_params();
+
+fn main() {
+    let _ = TREE;
+}
@@ -0,0 +1,7 @@
+use bvec::iter::IterMut;
+
+fn main() {}
+
+pub fn bar<'c, 'a>(x: IterMut<'c, &'static str>) -> IterMut<'c, &'a str> {
+    x
+}